@@ -0,0 +1,359 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::config::Config;
+use crate::reputation::{ReputationChangeReason, ReputationHistory, UserReputation};
+use crate::submission::ReportCategory;
+use crate::tiers::check_moderator_tier;
+use crate::{ErrorCode, ProgramState};
+
+#[derive(Accounts)]
+#[instruction(change_index: u64)]
+pub struct FileAppeal<'info> {
+    #[account(mut)]
+    pub appellant: Signer<'info>,
+    #[account(
+        seeds = [b"reputation_history", appellant.key().as_ref()],
+        bump = appellant_history.bump
+    )]
+    pub appellant_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        init,
+        payer = appellant,
+        space = 8 + Appeal::LEN,
+        seeds = [b"appeal", appellant_history.origin.as_ref(), change_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub appeal: Account<'info, Appeal>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, change_index: u64)]
+pub struct ResolveAppeal<'info> {
+    pub reviewer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"appeal", user_history.origin.as_ref(), change_index.to_le_bytes().as_ref()],
+        bump = appeal.bump,
+        constraint = appeal.status == AppealStatus::Pending @ ErrorCode::AppealNotPending
+    )]
+    pub appeal: Account<'info, Appeal>,
+    /// CHECK: receives the bond back when the appeal is upheld
+    #[account(mut, address = appeal.appellant @ ErrorCode::UserMismatch)]
+    pub appellant: AccountInfo<'info>,
+    /// Appeals filed before a migration are resolved against the new wallet
+    #[account(
+        mut,
+        seeds = [b"reputation", user.as_ref()],
+        bump,
+        constraint = user_reputation.migrated_to.is_none() @ ErrorCode::IdentityMigrated
+    )]
+    pub user_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", user.as_ref()],
+        bump = user_history.bump
+    )]
+    pub user_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Forfeited bonds accumulate on the state PDA on top of its rent.
+#[derive(Accounts)]
+pub struct WithdrawForfeits<'info> {
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+    /// CHECK: any account chosen by the authority to receive the lamports
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum AppealStatus {
+    Pending,
+    /// The penalty was reversed and the bond returned.
+    Upheld,
+    /// The penalty stands and the bond was forfeited to the program.
+    Denied,
+}
+
+/// Appeal against one penalty in the user's reputation history, at a PDA of
+/// the history's origin wallet. The account is kept after review so the same
+/// entry cannot be appealed twice, even from a migrated wallet.
+#[account]
+pub struct Appeal {
+    pub appellant: Pubkey,
+    pub bump: u8,
+    /// Sequence number of the appealed entry in `ReputationHistory`.
+    pub change_index: u64,
+    /// Penalty being appealed, copied from the history entry.
+    pub delta: i64,
+    /// Category score the penalty also lowered, and by how much.
+    pub category: Option<ReportCategory>,
+    pub category_delta: i64,
+    pub report_id: Option<u64>,
+    pub reason: String,
+    pub evidence_hash: [u8; 32],
+    /// Lamports held in this account on top of rent until review.
+    pub bond: u64,
+    pub filed_at: i64,
+    pub status: AppealStatus,
+    pub reviewed_by: Option<Pubkey>,
+}
+
+impl Appeal {
+    pub const MAX_REASON_LEN: usize = 256;
+
+    pub const LEN: usize = 32 // appellant
+        + 1 // bump
+        + 8 // change_index
+        + 8 // delta
+        + 1 + 1 // category
+        + 8 // category_delta
+        + 1 + 8 // report_id
+        + 4 + Self::MAX_REASON_LEN
+        + 32 // evidence_hash
+        + 8 // bond
+        + 8 // filed_at
+        + 1 // status
+        + 1 + 32; // reviewed_by
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct AppealParams {
+    /// Reviewers besides the program authority. `Pubkey::default()` marks an
+    /// empty slot.
+    pub moderators: [Pubkey; 5],
+    /// How long after a penalty it may be appealed.
+    pub appeal_window_seconds: i64,
+    pub bond_lamports: u64,
+}
+
+impl AppealParams {
+    pub const LEN: usize = 32 * 5 // moderators
+        + 8 // appeal_window_seconds
+        + 8; // bond_lamports
+
+    pub fn validate(&self) -> Result<()> {
+        if self.appeal_window_seconds <= 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        Ok(())
+    }
+
+    pub fn is_moderator(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.moderators.contains(key)
+    }
+}
+
+impl Default for AppealParams {
+    fn default() -> Self {
+        Self {
+            moderators: [Pubkey::default(); 5],
+            appeal_window_seconds: 14 * 24 * 60 * 60,
+            bond_lamports: 10_000_000,
+        }
+    }
+}
+
+pub fn file_appeal(ctx: Context<FileAppeal>, change_index: u64, reason: String, evidence_hash: [u8; 32]) -> Result<()> {
+    let appellant = &ctx.accounts.appellant;
+    let appeal = &mut ctx.accounts.appeal;
+    let params = &ctx.accounts.config.params.appeals;
+
+    if reason.len() > Appeal::MAX_REASON_LEN {
+        return Err(ErrorCode::InputTooLong.into());
+    }
+
+    // Only penalties still held in the history can be appealed
+    let entry = match ctx.accounts.appellant_history.entry(change_index) {
+        Some(entry) if entry.delta < 0 && entry.reason.is_appealable() && !entry.reversed => *entry,
+        _ => return Err(ErrorCode::NotAppealable.into()),
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    if now > entry.timestamp.saturating_add(params.appeal_window_seconds) {
+        return Err(ErrorCode::AppealWindowClosed.into());
+    }
+
+    // Hold the bond in the appeal account until review
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: appellant.to_account_info(),
+                to: appeal.to_account_info(),
+            },
+        ),
+        params.bond_lamports,
+    )?;
+
+    appeal.appellant = appellant.key();
+    appeal.bump = *ctx.bumps.get("appeal").unwrap();
+    appeal.change_index = change_index;
+    appeal.delta = entry.delta;
+    appeal.category = entry.category;
+    appeal.category_delta = entry.category_delta;
+    appeal.report_id = entry.report_id;
+    appeal.reason = reason;
+    appeal.evidence_hash = evidence_hash;
+    appeal.bond = params.bond_lamports;
+    appeal.filed_at = now;
+    appeal.status = AppealStatus::Pending;
+    appeal.reviewed_by = None;
+
+    // Emit an event for the appeal
+    emit!(AppealFiled {
+        appellant: appellant.key(),
+        change_index,
+        delta: entry.delta,
+        report_id: entry.report_id,
+        evidence_hash,
+    });
+
+    Ok(())
+}
+
+/// Upholding an appeal restores the global score by the appealed penalty,
+/// and the category score it lowered along with it. Users at the moderation tier review
+/// by passing their reputation account as the first remaining account.
+pub fn resolve_appeal<'info>(ctx: Context<'_, '_, '_, 'info, ResolveAppeal<'info>>, user: Pubkey, change_index: u64, uphold: bool) -> Result<()> {
+    let reviewer = &ctx.accounts.reviewer;
+    let appeal = &mut ctx.accounts.appeal;
+    let state = &mut ctx.accounts.state;
+    let config = &ctx.accounts.config.params;
+
+    // Appeals are reviewed by the program authority, a moderator or a
+    // moderation-tier user, never by the appellant
+    if reviewer.key() == appeal.appellant {
+        return Err(ErrorCode::Unauthorized.into());
+    }
+    if reviewer.key() != state.authority && !config.appeals.is_moderator(&reviewer.key()) {
+        check_moderator_tier(ctx.remaining_accounts, &reviewer.key(), config)?;
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // The bond goes back to the appellant if upheld and to the program if not
+    let bond_recipient = if uphold {
+        let user_reputation = &mut ctx.accounts.user_reputation;
+        user_reputation.apply_decay(config, now);
+        let restored_category = appeal.category.map(|category| {
+            let change = user_reputation.change_category_score(&category, -appeal.category_delta, config, now);
+            (category, change)
+        });
+        let old_score = user_reputation.reputation_score;
+        let restored_score = old_score
+            .saturating_sub(appeal.delta)
+            .clamp(config.min_reputation, config.max_reputation);
+        user_reputation.set_score(restored_score, now);
+        user_reputation.refresh_tier(&config.tiers);
+        let user_history = &mut ctx.accounts.user_history;
+        if let Some(entry) = user_history.entry_mut(change_index) {
+            entry.reversed = true;
+        }
+        let entry = user_history.record(
+            user_reputation.reputation_score - old_score,
+            ReputationChangeReason::AppealUpheld,
+            appeal.report_id,
+            reviewer.key(),
+            now,
+        );
+        if let (Some(entry), Some((category, change))) = (entry, restored_category) {
+            entry.set_category_change(category, change);
+        }
+        appeal.status = AppealStatus::Upheld;
+        ctx.accounts.appellant.to_account_info()
+    } else {
+        appeal.status = AppealStatus::Denied;
+        state.to_account_info()
+    };
+    appeal.reviewed_by = Some(reviewer.key());
+
+    let appeal_info = appeal.to_account_info();
+    **appeal_info.try_borrow_mut_lamports()? -= appeal.bond;
+    **bond_recipient.try_borrow_mut_lamports()? += appeal.bond;
+
+    // Emit an event for the review
+    emit!(AppealResolved {
+        appellant: user,
+        change_index,
+        status: appeal.status.clone(),
+        reviewed_by: reviewer.key(),
+    });
+
+    Ok(())
+}
+
+/// Sends forfeited appeal and submission bonds held by the state PDA to
+/// `recipient`, keeping the state account rent-exempt.
+pub fn withdraw_forfeits(ctx: Context<WithdrawForfeits>, amount: u64) -> Result<()> {
+    let state_info = ctx.accounts.state.to_account_info();
+    let recipient = &ctx.accounts.recipient;
+
+    let rent = Rent::get()?.minimum_balance(state_info.data_len());
+    let available = state_info.lamports().saturating_sub(rent);
+    if amount == 0 || amount > available {
+        return Err(ErrorCode::InsufficientFunds.into());
+    }
+
+    **state_info.try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? += amount;
+
+    // Emit an event for the withdrawal
+    emit!(ForfeitsWithdrawn {
+        recipient: recipient.key(),
+        amount,
+        withdrawn_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+// Event emitted when a user appeals a penalty
+#[event]
+pub struct AppealFiled {
+    pub appellant: Pubkey,
+    pub change_index: u64,
+    pub delta: i64,
+    pub report_id: Option<u64>,
+    pub evidence_hash: [u8; 32],
+}
+
+// Event emitted when an appeal is reviewed
+#[event]
+pub struct AppealResolved {
+    pub appellant: Pubkey,
+    pub change_index: u64,
+    pub status: AppealStatus,
+    pub reviewed_by: Pubkey,
+}
+
+// Event emitted when forfeited bonds are withdrawn
+#[event]
+pub struct ForfeitsWithdrawn {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub withdrawn_by: Pubkey,
+}
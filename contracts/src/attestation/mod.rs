@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use crate::config::Config;
+use crate::reputation::UserReputation;
+use crate::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct IssueAttestation<'info> {
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + Attestation::LEN,
+        seeds = [b"attestation", user.as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    #[account(
+        mut,
+        seeds = [b"reputation", user.as_ref()],
+        bump,
+        constraint = user_reputation.user == user @ ErrorCode::UserMismatch
+    )]
+    pub user_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct RevokeAttestation<'info> {
+    pub verifier: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"attestation", user.as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+    #[account(
+        mut,
+        seeds = [b"reputation", user.as_ref()],
+        bump,
+        constraint = user_reputation.user == user @ ErrorCode::UserMismatch
+    )]
+    pub user_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Proof that a verifier checked the user is a unique person. Reissuing
+/// replaces the previous attestation.
+#[account]
+pub struct Attestation {
+    pub user: Pubkey,
+    pub bump: u8,
+    pub issuer: Pubkey,
+    /// Hash identifying the kind of credential that was checked.
+    pub credential_type: [u8; 32],
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub revoked: bool,
+}
+
+impl Attestation {
+    pub const LEN: usize = 32 // user
+        + 1 // bump
+        + 32 // issuer
+        + 32 // credential_type
+        + 8 // issued_at
+        + 8 // expires_at
+        + 1; // revoked
+}
+
+pub fn issue_attestation(ctx: Context<IssueAttestation>, user: Pubkey, credential_type: [u8; 32], expires_at: i64) -> Result<()> {
+    let verifier = &ctx.accounts.verifier;
+    let attestation = &mut ctx.accounts.attestation;
+    let user_reputation = &mut ctx.accounts.user_reputation;
+
+    if !ctx.accounts.config.params.is_verifier(verifier.key) {
+        return Err(ErrorCode::NotVerifier.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if expires_at <= now {
+        return Err(ErrorCode::InvalidAttestationExpiry.into());
+    }
+
+    attestation.user = user;
+    attestation.bump = *ctx.bumps.get("attestation").unwrap();
+    attestation.issuer = verifier.key();
+    attestation.credential_type = credential_type;
+    attestation.issued_at = now;
+    attestation.expires_at = expires_at;
+    attestation.revoked = false;
+
+    user_reputation.attested_until = expires_at;
+
+    // Emit an event for the attestation
+    emit!(AttestationIssued {
+        user,
+        issuer: verifier.key(),
+        credential_type,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+pub fn revoke_attestation(ctx: Context<RevokeAttestation>, user: Pubkey) -> Result<()> {
+    let verifier = &ctx.accounts.verifier;
+    let attestation = &mut ctx.accounts.attestation;
+    let user_reputation = &mut ctx.accounts.user_reputation;
+
+    if !ctx.accounts.config.params.is_verifier(verifier.key) {
+        return Err(ErrorCode::NotVerifier.into());
+    }
+
+    if attestation.revoked {
+        return Err(ErrorCode::AttestationNotActive.into());
+    }
+
+    attestation.revoked = true;
+    user_reputation.attested_until = 0;
+
+    // Emit an event for the revocation
+    emit!(AttestationRevoked {
+        user,
+        revoked_by: verifier.key(),
+    });
+
+    Ok(())
+}
+
+// Event emitted when a verifier attests a user
+#[event]
+pub struct AttestationIssued {
+    pub user: Pubkey,
+    pub issuer: Pubkey,
+    pub credential_type: [u8; 32],
+    pub expires_at: i64,
+}
+
+// Event emitted when an attestation is revoked
+#[event]
+pub struct AttestationRevoked {
+    pub user: Pubkey,
+    pub revoked_by: Pubkey,
+}
@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+use crate::appeals::AppealParams;
+use crate::reputation::EligibilityParams;
+use crate::rewards::RewardParams;
+use crate::tiers::TierParams;
+use crate::voting::VotingParams;
+use crate::vouching::VouchParams;
+use crate::{ErrorCode, ProgramState};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
+}
+
+#[account]
+pub struct Config {
+    pub bump: u8,
+    pub params: ConfigParams,
+}
+
+impl Config {
+    pub const LEN: usize = 1 + ConfigParams::LEN;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ConfigParams {
+    /// Minimum net weighted votes for a report to be approved at finalization.
+    pub approval_threshold: i64,
+    pub voting: VotingParams,
+    pub rewards: RewardParams,
+    pub eligibility: EligibilityParams,
+    pub vouching: VouchParams,
+    pub appeals: AppealParams,
+    pub tiers: TierParams,
+    pub minimum_reputation_for_submission: i64,
+    /// Checked against the escalator's score in the report's category, on
+    /// top of the escalation tier.
+    pub minimum_reputation_for_escalation: i64,
+    /// Lamports held on a report until it is decided, unless the submitter's
+    /// tier waives it.
+    pub submission_bond_lamports: u64,
+    pub min_reputation: i64,
+    pub max_reputation: i64,
+    /// Time for an idle score to halve toward zero; zero disables decay.
+    pub reputation_half_life_seconds: i64,
+    pub upvote_reputation_change: i64,
+    pub downvote_reputation_change: i64,
+    pub report_approved_reputation_change: i64,
+    pub report_rejected_reputation_change: i64,
+    /// Members who may make fraud findings besides the program authority.
+    /// `Pubkey::default()` marks an empty slot.
+    pub fraud_council: [Pubkey; 5],
+    pub fraud_reputation_penalty: i64,
+    /// Authorities allowed to issue and revoke personhood attestations.
+    /// `Pubkey::default()` marks an empty slot.
+    pub verifiers: [Pubkey; 5],
+}
+
+impl ConfigParams {
+    pub const LEN: usize = 8 // approval_threshold
+        + VotingParams::LEN
+        + RewardParams::LEN
+        + EligibilityParams::LEN
+        + VouchParams::LEN
+        + AppealParams::LEN
+        + TierParams::LEN
+        + 8 // minimum_reputation_for_submission
+        + 8 // minimum_reputation_for_escalation
+        + 8 // submission_bond_lamports
+        + 8 // min_reputation
+        + 8 // max_reputation
+        + 8 // reputation_half_life_seconds
+        + 8 // upvote_reputation_change
+        + 8 // downvote_reputation_change
+        + 8 // report_approved_reputation_change
+        + 8 // report_rejected_reputation_change
+        + 32 * 5 // fraud_council
+        + 8 // fraud_reputation_penalty
+        + 32 * 5; // verifiers
+
+    /// Replaces the settings kept outside the sections with `core`.
+    pub fn set_core(&mut self, core: CoreParams) {
+        let CoreParams {
+            approval_threshold,
+            minimum_reputation_for_submission,
+            minimum_reputation_for_escalation,
+            submission_bond_lamports,
+            min_reputation,
+            max_reputation,
+            reputation_half_life_seconds,
+            upvote_reputation_change,
+            downvote_reputation_change,
+            report_approved_reputation_change,
+            report_rejected_reputation_change,
+            fraud_council,
+            fraud_reputation_penalty,
+            verifiers,
+        } = core;
+        let ConfigParams { voting, rewards, eligibility, vouching, appeals, tiers, .. } = std::mem::take(self);
+        *self = ConfigParams {
+            approval_threshold,
+            voting,
+            rewards,
+            eligibility,
+            vouching,
+            appeals,
+            tiers,
+            minimum_reputation_for_submission,
+            minimum_reputation_for_escalation,
+            submission_bond_lamports,
+            min_reputation,
+            max_reputation,
+            reputation_half_life_seconds,
+            upvote_reputation_change,
+            downvote_reputation_change,
+            report_approved_reputation_change,
+            report_rejected_reputation_change,
+            fraud_council,
+            fraud_reputation_penalty,
+            verifiers,
+        };
+    }
+
+    pub fn is_council_member(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.fraud_council.contains(key)
+    }
+
+    pub fn is_verifier(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.verifiers.contains(key)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let reputation_range = self.min_reputation..=self.max_reputation;
+
+        if self.approval_threshold <= 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        if self.min_reputation > 0 || self.max_reputation < 0 || self.reputation_half_life_seconds < 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        if !reputation_range.contains(&self.minimum_reputation_for_submission)
+            || !reputation_range.contains(&self.minimum_reputation_for_escalation)
+            || !reputation_range.contains(&self.tiers.steward_threshold)
+            || !reputation_range.contains(&self.eligibility.minimum_reputation_for_voting)
+            || !reputation_range.contains(&self.vouching.minimum_reputation_for_vouching)
+            || !reputation_range.contains(&self.vouching.slash_threshold)
+        {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+
+        // Reputation deltas are magnitudes; the sign is applied by the caller
+        let deltas = [
+            self.upvote_reputation_change,
+            self.downvote_reputation_change,
+            self.report_approved_reputation_change,
+            self.report_rejected_reputation_change,
+            self.fraud_reputation_penalty,
+        ];
+        if deltas.iter().any(|delta| *delta < 0) {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+
+        self.voting.validate()?;
+        self.eligibility.validate()?;
+        self.vouching.validate()?;
+        self.appeals.validate()?;
+        self.tiers.validate()?;
+        self.rewards.validate()
+    }
+}
+
+impl Default for ConfigParams {
+    fn default() -> Self {
+        Self {
+            approval_threshold: 5,
+            voting: VotingParams::default(),
+            rewards: RewardParams::default(),
+            eligibility: EligibilityParams::default(),
+            vouching: VouchParams::default(),
+            appeals: AppealParams::default(),
+            tiers: TierParams::default(),
+            minimum_reputation_for_submission: -500,
+            minimum_reputation_for_escalation: 100,
+            submission_bond_lamports: 5_000_000,
+            min_reputation: -1000,
+            max_reputation: 1000,
+            reputation_half_life_seconds: 180 * 24 * 60 * 60,
+            upvote_reputation_change: 5,
+            downvote_reputation_change: 2,
+            report_approved_reputation_change: 20,
+            report_rejected_reputation_change: 10,
+            fraud_council: [Pubkey::default(); 5],
+            fraud_reputation_penalty: 100,
+            verifiers: [Pubkey::default(); 5],
+        }
+    }
+}
+
+/// The settings of `ConfigParams` that are not part of a section, updated
+/// together by `update_core_params`. Field meanings are documented on
+/// `ConfigParams`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct CoreParams {
+    pub approval_threshold: i64,
+    pub minimum_reputation_for_submission: i64,
+    pub minimum_reputation_for_escalation: i64,
+    pub submission_bond_lamports: u64,
+    pub min_reputation: i64,
+    pub max_reputation: i64,
+    pub reputation_half_life_seconds: i64,
+    pub upvote_reputation_change: i64,
+    pub downvote_reputation_change: i64,
+    pub report_approved_reputation_change: i64,
+    pub report_rejected_reputation_change: i64,
+    pub fraud_council: [Pubkey; 5],
+    pub fraud_reputation_penalty: i64,
+    pub verifiers: [Pubkey; 5],
+}
+
+// The whole configuration does not fit in one transaction, so it starts from
+// the defaults and is updated one section at a time.
+
+pub fn update_core_params(ctx: Context<UpdateConfig>, core: CoreParams) -> Result<()> {
+    update_config(ctx, |params| params.set_core(core))
+}
+
+pub fn update_voting_params(ctx: Context<UpdateConfig>, voting: VotingParams) -> Result<()> {
+    update_config(ctx, |params| params.voting = voting)
+}
+
+pub fn update_reward_params(ctx: Context<UpdateConfig>, rewards: RewardParams) -> Result<()> {
+    update_config(ctx, |params| params.rewards = rewards)
+}
+
+pub fn update_eligibility_params(ctx: Context<UpdateConfig>, eligibility: EligibilityParams) -> Result<()> {
+    update_config(ctx, |params| params.eligibility = eligibility)
+}
+
+pub fn update_vouch_params(ctx: Context<UpdateConfig>, vouching: VouchParams) -> Result<()> {
+    update_config(ctx, |params| params.vouching = vouching)
+}
+
+pub fn update_appeal_params(ctx: Context<UpdateConfig>, appeals: AppealParams) -> Result<()> {
+    update_config(ctx, |params| params.appeals = appeals)
+}
+
+pub fn update_tier_params(ctx: Context<UpdateConfig>, tiers: TierParams) -> Result<()> {
+    update_config(ctx, |params| params.tiers = tiers)
+}
+
+/// Applies `change` to a copy of the current parameters and stores it if the
+/// result is still valid as a whole.
+fn update_config(ctx: Context<UpdateConfig>, change: impl FnOnce(&mut ConfigParams)) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let authority = &ctx.accounts.authority;
+
+    let mut params = config.params.clone();
+    change(&mut params);
+    params.validate()?;
+
+    let old_params = std::mem::replace(&mut config.params, params.clone());
+
+    // Emit an event for the configuration change
+    emit!(ConfigUpdated {
+        updated_by: *authority.key,
+        old_params,
+        new_params: params,
+    });
+
+    msg!("Config updated by: {}", authority.key);
+
+    Ok(())
+}
+
+// Event emitted when the program configuration is changed
+#[event]
+pub struct ConfigUpdated {
+    pub updated_by: Pubkey,
+    pub old_params: ConfigParams,
+    pub new_params: ConfigParams,
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::submission::ReportCategory;
+use crate::ErrorCode;
+
+#[derive(Accounts)]
+pub struct DelegateVotes<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = 8 + Delegation::LEN,
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    pub delegator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"delegation", delegator.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
+
+/// A user's voting delegates. A category entry takes precedence over the
+/// global delegate for reports in that category.
+#[account]
+pub struct Delegation {
+    pub delegator: Pubkey,
+    pub bump: u8,
+    pub global_delegate: Option<Pubkey>,
+    /// Indexed by `ReportCategory::index`.
+    pub category_delegates: [Option<Pubkey>; ReportCategory::COUNT],
+}
+
+impl Delegation {
+    pub const LEN: usize = 32 // delegator
+        + 1 // bump
+        + (1 + 32) // global_delegate
+        + (1 + 32) * ReportCategory::COUNT; // category_delegates
+
+    /// Delegate who votes on the delegator's behalf in `category`, if any.
+    pub fn delegate_for(&self, category: &ReportCategory) -> Option<Pubkey> {
+        self.category_delegates[category.index()].or(self.global_delegate)
+    }
+
+    fn slot(&mut self, category: &Option<ReportCategory>) -> &mut Option<Pubkey> {
+        match category {
+            Some(category) => &mut self.category_delegates[category.index()],
+            None => &mut self.global_delegate,
+        }
+    }
+}
+
+pub fn delegate_votes(ctx: Context<DelegateVotes>, delegate: Pubkey, category: Option<ReportCategory>) -> Result<()> {
+    let delegator = &ctx.accounts.delegator;
+    let delegation = &mut ctx.accounts.delegation;
+
+    if delegate == delegator.key() {
+        return Err(ErrorCode::SelfDelegation.into());
+    }
+
+    delegation.delegator = delegator.key();
+    delegation.bump = *ctx.bumps.get("delegation").unwrap();
+    let previous_delegate = delegation.slot(&category).replace(delegate);
+
+    // Emit an event for the delegation
+    emit!(VotesDelegated {
+        delegator: delegator.key(),
+        delegate,
+        previous_delegate,
+        category,
+    });
+
+    Ok(())
+}
+
+pub fn revoke_delegation(ctx: Context<RevokeDelegation>, category: Option<ReportCategory>) -> Result<()> {
+    let delegator = &ctx.accounts.delegator;
+    let delegation = &mut ctx.accounts.delegation;
+
+    let delegate = match delegation.slot(&category).take() {
+        Some(delegate) => delegate,
+        None => return Err(ErrorCode::NoDelegation.into()),
+    };
+
+    // Emit an event for the revocation
+    emit!(DelegationRevoked {
+        delegator: delegator.key(),
+        delegate,
+        category,
+    });
+
+    Ok(())
+}
+
+// Event emitted when a user delegates their voting weight
+#[event]
+pub struct VotesDelegated {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub previous_delegate: Option<Pubkey>,
+    pub category: Option<ReportCategory>,
+}
+
+// Event emitted when a user revokes a delegation
+#[event]
+pub struct DelegationRevoked {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub category: Option<ReportCategory>,
+}
@@ -1,21 +1,32 @@
 use anchor_lang::prelude::*;
-use crate::submission::{Report, ReportStatus};
-use crate::reputation::get_user_reputation;
-use crate::ErrorCode;
+use crate::config::Config;
+use crate::submission::{transition_report_status, Report, ReportStatus};
+use crate::reputation::UserReputation;
+use crate::{ErrorCode, ProgramState};
 
 #[derive(Accounts)]
 #[instruction(report_id: u64)]
 pub struct EscalateReport<'info> {
     #[account(
         mut,
-        constraint = report.key() == report_id @ ErrorCode::ReportNotFound,
-        constraint = report.status == ReportStatus::Submitted || report.status == ReportStatus::UnderReview @ ErrorCode::InvalidStatusTransition
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
+        constraint = report.status.can_transition_to(&ReportStatus::UnderReview) @ ErrorCode::InvalidStatusTransition
     )]
     pub report: Account<'info, Report>,
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(seeds = [b"authority".as_ref()], bump)]
-    pub program_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"reputation", user.key().as_ref()],
+        bump
+    )]
+    pub user_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
     pub system_program: Program<'info, System>,
 }
 
@@ -38,35 +49,62 @@ pub struct EscalationDetails {
     pub resolution_details: Option<String>,
 }
 
+impl EscalationDetails {
+    pub const MAX_DESCRIPTION_LEN: usize = 256;
+    pub const MAX_RESOLUTION_LEN: usize = 256;
+
+    pub const LEN: usize = 1 // reason
+        + 4 + Self::MAX_DESCRIPTION_LEN
+        + 8 // escalated_at
+        + 32 // escalated_by
+        + 1 // resolved
+        + 1 + 4 + Self::MAX_RESOLUTION_LEN;
+}
+
 pub fn escalate_report(ctx: Context<EscalateReport>, report_id: u64, reason: EscalationReason, description: String) -> Result<()> {
     let report = &mut ctx.accounts.report;
     let user = &ctx.accounts.user;
+    let user_reputation = &mut ctx.accounts.user_reputation;
+
+    if description.len() > EscalationDetails::MAX_DESCRIPTION_LEN {
+        return Err(ErrorCode::InputTooLong.into());
+    }
+
+    let config = &ctx.accounts.config.params;
+    let now = Clock::get()?.unix_timestamp;
+    user_reputation.apply_decay(config, now);
+
+    // Check if the user's tier allows escalation
+    let tier = config.tiers.tier_for(user_reputation.reputation_score);
+    if !config.tiers.permissions(tier).can_escalate {
+        return Err(ErrorCode::InsufficientTier.into());
+    }
 
-    // Check if the user has sufficient reputation to escalate
-    let user_reputation = get_user_reputation(user.key())?;
-    if user_reputation < MINIMUM_REPUTATION_FOR_ESCALATION {
+    // Check if the user has sufficient reputation in the report's category
+    if user_reputation.score_for(&report.category) < config.minimum_reputation_for_escalation {
         return Err(ErrorCode::InsufficientReputation.into());
     }
+    config.eligibility.check_escalator(user_reputation, now)?;
 
     // Create escalation details
     let escalation_details = EscalationDetails {
-        reason,
+        reason: reason.clone(),
         description,
-        escalated_at: Clock::get()?.unix_timestamp,
+        escalated_at: now,
         escalated_by: *user.key,
         resolved: false,
         resolution_details: None,
     };
 
     // Update report status and add escalation details
-    report.status = ReportStatus::UnderReview;
+    transition_report_status(report, ReportStatus::UnderReview)?;
     report.escalation = Some(escalation_details);
 
     // Emit an event for the escalation
     emit!(ReportEscalated {
         report_id,
         escalated_by: *user.key,
-        reason: reason.clone(),
+        reason,
     });
 
     Ok(())
@@ -74,11 +112,10 @@ pub fn escalate_report(ctx: Context<EscalateReport>, report_id: u64, reason: Esc
 
 pub fn resolve_escalation(ctx: Context<ResolveEscalation>, report_id: u64, resolution_details: String) -> Result<()> {
     let report = &mut ctx.accounts.report;
-    let authority = &ctx.accounts.program_authority;
+    let authority = &ctx.accounts.authority;
 
-    // Ensure only the program authority can resolve escalations
-    if !authority.is_signer {
-        return Err(ErrorCode::Unauthorized.into());
+    if resolution_details.len() > EscalationDetails::MAX_RESOLUTION_LEN {
+        return Err(ErrorCode::InputTooLong.into());
     }
 
     // Update escalation details
@@ -90,7 +127,8 @@ pub fn resolve_escalation(ctx: Context<ResolveEscalation>, report_id: u64, resol
     }
 
     // Update report status
-    report.status = ReportStatus::Resolved;
+    transition_report_status(report, ReportStatus::Resolved)?;
+    ctx.accounts.submitter_reputation.report_closed();
 
     // Emit an event for the resolution
     emit!(EscalationResolved {
@@ -106,12 +144,24 @@ pub fn resolve_escalation(ctx: Context<ResolveEscalation>, report_id: u64, resol
 pub struct ResolveEscalation<'info> {
     #[account(
         mut,
-        constraint = report.key() == report_id @ ErrorCode::ReportNotFound,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
         constraint = report.status == ReportStatus::UnderReview @ ErrorCode::InvalidStatusTransition
     )]
     pub report: Account<'info, Report>,
-    #[account(seeds = [b"authority".as_ref()], bump)]
-    pub program_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
 }
 
 // Event emitted when a report is escalated
@@ -127,7 +177,4 @@ pub struct ReportEscalated {
 pub struct EscalationResolved {
     pub report_id: u64,
     pub resolved_by: Pubkey,
-}
-
-// Constants
-const MINIMUM_REPUTATION_FOR_ESCALATION: i64 = 100; // Adjust this value as needed
\ No newline at end of file
+}
\ No newline at end of file
@@ -0,0 +1,275 @@
+use anchor_lang::prelude::*;
+use crate::attestation::Attestation;
+use crate::reputation::{ReputationHistory, UserReputation};
+use crate::rewards::RewardLedger;
+use crate::tiers::ReputationTier;
+use crate::vouching::{Vouch, VouchStatus};
+use crate::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Guardians::LEN,
+        seeds = [b"guardians", user.key().as_ref()],
+        bump
+    )]
+    pub guardians: Account<'info, Guardians>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Remaining accounts are the signers approving the migration: either the
+/// old wallet itself or enough of its guardians. Accounts the old wallet
+/// never opened are created empty so the instruction has a fixed shape.
+#[derive(Accounts)]
+#[instruction(old_user: Pubkey)]
+pub struct MigrateIdentity<'info> {
+    #[account(mut)]
+    pub new_user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"reputation", old_user.as_ref()],
+        bump,
+        constraint = old_reputation.migrated_to.is_none() @ ErrorCode::IdentityMigrated,
+        constraint = old_reputation.active_vouches == 0 @ ErrorCode::MigrationBlocked,
+        constraint = old_reputation.open_reports == 0 @ ErrorCode::MigrationBlocked
+    )]
+    pub old_reputation: Box<Account<'info, UserReputation>>,
+    /// CHECK: vouch for the old wallet as a newcomer, if one was ever made;
+    /// only read when the program owns it
+    #[account(
+        seeds = [b"vouch", old_user.as_ref()],
+        bump
+    )]
+    pub old_vouch: AccountInfo<'info>,
+    #[account(
+        seeds = [b"reputation_history", old_user.as_ref()],
+        bump = old_history.bump
+    )]
+    pub old_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        init,
+        payer = new_user,
+        space = 8 + UserReputation::LEN,
+        seeds = [b"reputation", new_user.key().as_ref()],
+        bump
+    )]
+    pub new_reputation: Box<Account<'info, UserReputation>>,
+    #[account(
+        init,
+        payer = new_user,
+        space = 8 + ReputationHistory::LEN,
+        seeds = [b"reputation_history", new_user.key().as_ref()],
+        bump
+    )]
+    pub new_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        init_if_needed,
+        payer = new_user,
+        space = 8 + RewardLedger::LEN,
+        seeds = [b"reward_ledger", old_user.as_ref()],
+        bump
+    )]
+    pub old_reward_ledger: Box<Account<'info, RewardLedger>>,
+    #[account(
+        init_if_needed,
+        payer = new_user,
+        space = 8 + RewardLedger::LEN,
+        seeds = [b"reward_ledger", new_user.key().as_ref()],
+        bump
+    )]
+    pub new_reward_ledger: Box<Account<'info, RewardLedger>>,
+    #[account(
+        init_if_needed,
+        payer = new_user,
+        space = 8 + Attestation::LEN,
+        seeds = [b"attestation", old_user.as_ref()],
+        bump
+    )]
+    pub old_attestation: Box<Account<'info, Attestation>>,
+    #[account(
+        init_if_needed,
+        payer = new_user,
+        space = 8 + Attestation::LEN,
+        seeds = [b"attestation", new_user.key().as_ref()],
+        bump
+    )]
+    pub new_attestation: Box<Account<'info, Attestation>>,
+    #[account(
+        init_if_needed,
+        payer = new_user,
+        space = 8 + Guardians::LEN,
+        seeds = [b"guardians", old_user.as_ref()],
+        bump
+    )]
+    pub guardians: Box<Account<'info, Guardians>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Wallets that can jointly move a user's identity if their key is lost.
+#[account]
+pub struct Guardians {
+    pub user: Pubkey,
+    pub bump: u8,
+    /// `Pubkey::default()` marks an empty slot.
+    pub guardians: [Pubkey; 5],
+    /// Approvals needed for a recovery; zero disables guardian recovery.
+    pub threshold: u8,
+}
+
+impl Guardians {
+    pub const LEN: usize = 32 // user
+        + 1 // bump
+        + 32 * 5 // guardians
+        + 1; // threshold
+
+    fn approvals(&self, signers: &[Pubkey]) -> usize {
+        self.guardians
+            .iter()
+            .filter(|guardian| **guardian != Pubkey::default() && signers.contains(guardian))
+            .count()
+    }
+}
+
+pub fn set_guardians(ctx: Context<SetGuardians>, guardians: [Pubkey; 5], threshold: u8) -> Result<()> {
+    let user = &ctx.accounts.user;
+    let account = &mut ctx.accounts.guardians;
+
+    // Guardians must be distinct from each other and from the user
+    let members: Vec<&Pubkey> = guardians.iter().filter(|g| **g != Pubkey::default()).collect();
+    let distinct = members
+        .iter()
+        .enumerate()
+        .all(|(i, guardian)| **guardian != user.key() && !members[i + 1..].contains(guardian));
+    if !distinct || threshold as usize > members.len() {
+        return Err(ErrorCode::InvalidGuardians.into());
+    }
+
+    account.user = user.key();
+    account.bump = *ctx.bumps.get("guardians").unwrap();
+    account.guardians = guardians;
+    account.threshold = threshold;
+
+    // Emit an event for the guardian change
+    emit!(GuardiansUpdated {
+        user: user.key(),
+        guardians,
+        threshold,
+    });
+
+    Ok(())
+}
+
+/// Moves reputation, history, reward grants and the attestation of
+/// `old_user` to the signing new wallet and tombstones the old reputation
+/// account. Delegations and unclaimed validator rewards stay with the old
+/// wallet; vouches and reports involving it must be settled first, since
+/// they settle against the old wallet's accounts.
+pub fn migrate_identity<'info>(ctx: Context<'_, '_, '_, 'info, MigrateIdentity<'info>>, old_user: Pubkey) -> Result<()> {
+    let new_user = ctx.accounts.new_user.key();
+
+    let signers: Vec<Pubkey> = ctx
+        .remaining_accounts
+        .iter()
+        .filter(|account| account.is_signer)
+        .map(|account| account.key())
+        .collect();
+    let by_guardians = !signers.contains(&old_user);
+    if by_guardians {
+        let guardians = &ctx.accounts.guardians;
+        if guardians.threshold == 0 || guardians.approvals(&signers) < guardians.threshold as usize {
+            return Err(ErrorCode::MigrationNotAuthorized.into());
+        }
+    }
+
+    // An active vouch for the old wallet settles against its reputation
+    // account, so the newcomer could otherwise escape a slash by migrating
+    let old_vouch = &ctx.accounts.old_vouch;
+    if *old_vouch.owner == crate::ID && !old_vouch.data_is_empty() {
+        let vouch = Account::<Vouch>::try_from(old_vouch)?;
+        if vouch.status == VouchStatus::Active {
+            return Err(ErrorCode::MigrationBlocked.into());
+        }
+    }
+
+    // Copy reputation and history to the new wallet
+    let old_reputation = &mut ctx.accounts.old_reputation;
+    **ctx.accounts.new_reputation = UserReputation {
+        user: new_user,
+        migrated_to: None,
+        ..(***old_reputation).clone()
+    };
+    let old_history = &ctx.accounts.old_history;
+    let new_history = &mut ctx.accounts.new_history;
+    new_history.user = new_user;
+    new_history.bump = *ctx.bumps.get("new_history").unwrap();
+    new_history.origin = old_history.origin;
+    new_history.total_changes = old_history.total_changes;
+    new_history.entries = old_history.entries;
+
+    // Tombstone the old account
+    old_reputation.reputation_score = 0;
+    old_reputation.category_scores = Default::default();
+    old_reputation.attested_until = 0;
+    old_reputation.tier = ReputationTier::Newcomer;
+    old_reputation.migrated_to = Some(new_user);
+
+    // Move outstanding reward grants
+    let old_reward_ledger = &mut ctx.accounts.old_reward_ledger;
+    if old_reward_ledger.owner == Pubkey::default() {
+        old_reward_ledger.owner = old_user;
+        old_reward_ledger.bump = *ctx.bumps.get("old_reward_ledger").unwrap();
+    }
+    let new_reward_ledger = &mut ctx.accounts.new_reward_ledger;
+    if new_reward_ledger.owner == Pubkey::default() {
+        new_reward_ledger.owner = new_user;
+        new_reward_ledger.bump = *ctx.bumps.get("new_reward_ledger").unwrap();
+    }
+    new_reward_ledger.take_grants_from(old_reward_ledger)?;
+
+    // Move the attestation, leaving the old one revoked
+    let old_attestation = &mut ctx.accounts.old_attestation;
+    let new_attestation = &mut ctx.accounts.new_attestation;
+    if old_attestation.user != Pubkey::default() && !old_attestation.revoked {
+        new_attestation.issuer = old_attestation.issuer;
+        new_attestation.credential_type = old_attestation.credential_type;
+        new_attestation.issued_at = old_attestation.issued_at;
+        new_attestation.expires_at = old_attestation.expires_at;
+        new_attestation.revoked = false;
+    } else if new_attestation.user == Pubkey::default() {
+        new_attestation.revoked = true;
+    }
+    new_attestation.user = new_user;
+    new_attestation.bump = *ctx.bumps.get("new_attestation").unwrap();
+    old_attestation.user = old_user;
+    old_attestation.bump = *ctx.bumps.get("old_attestation").unwrap();
+    old_attestation.revoked = true;
+
+    // Emit an event for the migration
+    emit!(IdentityMigrated {
+        old_user,
+        new_user,
+        by_guardians,
+    });
+
+    Ok(())
+}
+
+// Event emitted when a user sets their recovery guardians
+#[event]
+pub struct GuardiansUpdated {
+    pub user: Pubkey,
+    pub guardians: [Pubkey; 5],
+    pub threshold: u8,
+}
+
+// Event emitted when an identity moves to a new wallet
+#[event]
+pub struct IdentityMigrated {
+    pub old_user: Pubkey,
+    pub new_user: Pubkey,
+    pub by_guardians: bool,
+}
@@ -1,17 +1,32 @@
+#![allow(clippy::result_large_err)]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
 
-mod submission;
-mod voting;
-mod rewards;
-mod reputation;
-mod escalation;
+pub mod config;
+pub mod submission;
+pub mod voting;
+pub mod rewards;
+pub mod reputation;
+pub mod escalation;
+pub mod delegation;
+pub mod attestation;
+pub mod vouching;
+pub mod appeals;
+pub mod identity;
+pub mod tiers;
 
+use config::*;
 use submission::*;
 use voting::*;
 use rewards::*;
 use reputation::*;
 use escalation::*;
+use delegation::*;
+use attestation::*;
+use vouching::*;
+use appeals::*;
+use identity::*;
+use tiers::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
@@ -19,15 +34,52 @@ declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 pub mod civicaid_dao {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>, bump: u8) -> Result<()> {
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let state = &mut ctx.accounts.state;
         state.authority = ctx.accounts.authority.key();
-        state.bump = bump;
+        state.bump = *ctx.bumps.get("state").unwrap();
         state.report_count = 0;
         state.total_rewards_distributed = 0;
+        state.total_rewards_claimed = 0;
+        state.total_rewards_clawed_back = 0;
+
+        let config = &mut ctx.accounts.config;
+        config.bump = *ctx.bumps.get("config").unwrap();
+        config.params = ConfigParams::default();
         Ok(())
     }
 
+    pub fn update_core_params(ctx: Context<UpdateConfig>, core: CoreParams) -> Result<()> {
+        config::update_core_params(ctx, core)
+    }
+
+    pub fn update_voting_params(ctx: Context<UpdateConfig>, voting: VotingParams) -> Result<()> {
+        config::update_voting_params(ctx, voting)
+    }
+
+    pub fn update_reward_params(ctx: Context<UpdateConfig>, rewards: RewardParams) -> Result<()> {
+        config::update_reward_params(ctx, rewards)
+    }
+
+    pub fn update_eligibility_params(
+        ctx: Context<UpdateConfig>,
+        eligibility: EligibilityParams,
+    ) -> Result<()> {
+        config::update_eligibility_params(ctx, eligibility)
+    }
+
+    pub fn update_vouch_params(ctx: Context<UpdateConfig>, vouching: VouchParams) -> Result<()> {
+        config::update_vouch_params(ctx, vouching)
+    }
+
+    pub fn update_appeal_params(ctx: Context<UpdateConfig>, appeals: AppealParams) -> Result<()> {
+        config::update_appeal_params(ctx, appeals)
+    }
+
+    pub fn update_tier_params(ctx: Context<UpdateConfig>, tiers: TierParams) -> Result<()> {
+        config::update_tier_params(ctx, tiers)
+    }
+
     pub fn submit_report(
         ctx: Context<SubmitReport>,
         description: String,
@@ -38,20 +90,131 @@ pub mod civicaid_dao {
         submission::submit_report(ctx, description, location, media_hash, category)
     }
 
-    pub fn vote_on_report(
-        ctx: Context<VoteOnReport>,
+    pub fn release_submission_bond(
+        ctx: Context<ReleaseSubmissionBond>,
+        report_id: u64,
+    ) -> Result<()> {
+        submission::release_submission_bond(ctx, report_id)
+    }
+
+    pub fn vote_on_report<'info>(
+        ctx: Context<'_, '_, '_, 'info, VoteOnReport<'info>>,
         report_id: u64,
         vote_type: VoteType,
     ) -> Result<()> {
         voting::vote_on_report(ctx, report_id, vote_type)
     }
 
+    pub fn cast_quadratic_vote(
+        ctx: Context<CastQuadraticVote>,
+        report_id: u64,
+        vote_type: VoteType,
+        intensity: u64,
+    ) -> Result<()> {
+        voting::cast_quadratic_vote(ctx, report_id, vote_type, intensity)
+    }
+
+    pub fn change_vote(
+        ctx: Context<ChangeVote>,
+        report_id: u64,
+        new_vote_type: VoteType,
+    ) -> Result<()> {
+        voting::change_vote(ctx, report_id, new_vote_type)
+    }
+
+    pub fn retract_vote(ctx: Context<RetractVote>, report_id: u64) -> Result<()> {
+        voting::retract_vote(ctx, report_id)
+    }
+
+    pub fn commit_vote(
+        ctx: Context<CommitVote>,
+        report_id: u64,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        voting::commit_vote(ctx, report_id, commitment)
+    }
+
+    pub fn reveal_vote(
+        ctx: Context<RevealVote>,
+        report_id: u64,
+        vote_type: VoteType,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        voting::reveal_vote(ctx, report_id, vote_type, salt)
+    }
+
+    pub fn forfeit_unrevealed_vote(
+        ctx: Context<ForfeitUnrevealedVote>,
+        report_id: u64,
+        voter: Pubkey,
+    ) -> Result<()> {
+        voting::forfeit_unrevealed_vote(ctx, report_id, voter)
+    }
+
+    pub fn delegate_votes(
+        ctx: Context<DelegateVotes>,
+        delegate: Pubkey,
+        category: Option<ReportCategory>,
+    ) -> Result<()> {
+        delegation::delegate_votes(ctx, delegate, category)
+    }
+
+    pub fn revoke_delegation(
+        ctx: Context<RevokeDelegation>,
+        category: Option<ReportCategory>,
+    ) -> Result<()> {
+        delegation::revoke_delegation(ctx, category)
+    }
+
+    pub fn finalize_report(ctx: Context<FinalizeReport>, report_id: u64) -> Result<()> {
+        voting::finalize_report(ctx, report_id)
+    }
+
+    pub fn settle_votes<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleVotes<'info>>,
+        report_id: u64,
+    ) -> Result<()> {
+        voting::settle_votes(ctx, report_id)
+    }
+
+    pub fn initialize_reward_vault(
+        ctx: Context<InitializeRewardVault>,
+        amount: u64,
+    ) -> Result<()> {
+        rewards::initialize_reward_vault(ctx, amount)
+    }
+
     pub fn distribute_rewards(
         ctx: Context<DistributeRewards>,
         report_id: u64,
-        amount: u64,
     ) -> Result<()> {
-        rewards::distribute_rewards(ctx, report_id, amount)
+        rewards::distribute_rewards(ctx, report_id)
+    }
+
+    pub fn claim_validator_reward(
+        ctx: Context<ClaimValidatorReward>,
+        report_id: u64,
+    ) -> Result<()> {
+        rewards::claim_validator_reward(ctx, report_id)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        rewards::claim_rewards(ctx)
+    }
+
+    pub fn flag_fraudulent_report<'info>(
+        ctx: Context<'_, '_, '_, 'info, FlagFraudulentReport<'info>>,
+        report_id: u64,
+    ) -> Result<()> {
+        rewards::flag_fraudulent_report(ctx, report_id)
+    }
+
+    pub fn quote_reward(ctx: Context<QuoteReward>, report_id: u64) -> Result<u64> {
+        rewards::quote_reward(ctx, report_id)
+    }
+
+    pub fn initialize_user_reputation(ctx: Context<InitializeUserReputation>) -> Result<()> {
+        reputation::initialize_user_reputation(ctx)
     }
 
     pub fn update_reputation(
@@ -62,12 +225,101 @@ pub mod civicaid_dao {
         reputation::update_reputation(ctx, user, change)
     }
 
+    pub fn issue_attestation(
+        ctx: Context<IssueAttestation>,
+        user: Pubkey,
+        credential_type: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        attestation::issue_attestation(ctx, user, credential_type, expires_at)
+    }
+
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>, user: Pubkey) -> Result<()> {
+        attestation::revoke_attestation(ctx, user)
+    }
+
+    pub fn get_effective_reputation(
+        ctx: Context<GetEffectiveReputation>,
+        user: Pubkey,
+    ) -> Result<i64> {
+        reputation::get_effective_reputation(ctx, user)
+    }
+
+    pub fn get_reputation_tier(
+        ctx: Context<GetReputationTier>,
+        user: Pubkey,
+    ) -> Result<TierStatus> {
+        tiers::get_reputation_tier(ctx, user)
+    }
+
+    pub fn vouch_for_user(
+        ctx: Context<VouchForUser>,
+        newcomer: Pubkey,
+        stake: i64,
+    ) -> Result<()> {
+        vouching::vouch_for_user(ctx, newcomer, stake)
+    }
+
+    pub fn release_vouch(ctx: Context<SettleVouch>, newcomer: Pubkey) -> Result<()> {
+        vouching::release_vouch(ctx, newcomer)
+    }
+
+    pub fn slash_vouch(ctx: Context<SettleVouch>, newcomer: Pubkey) -> Result<()> {
+        vouching::slash_vouch(ctx, newcomer)
+    }
+
+    pub fn file_appeal(
+        ctx: Context<FileAppeal>,
+        change_index: u64,
+        reason: String,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        appeals::file_appeal(ctx, change_index, reason, evidence_hash)
+    }
+
+    pub fn resolve_appeal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveAppeal<'info>>,
+        user: Pubkey,
+        change_index: u64,
+        uphold: bool,
+    ) -> Result<()> {
+        appeals::resolve_appeal(ctx, user, change_index, uphold)
+    }
+
+    pub fn withdraw_forfeits(ctx: Context<WithdrawForfeits>, amount: u64) -> Result<()> {
+        appeals::withdraw_forfeits(ctx, amount)
+    }
+
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: [Pubkey; 5],
+        threshold: u8,
+    ) -> Result<()> {
+        identity::set_guardians(ctx, guardians, threshold)
+    }
+
+    pub fn migrate_identity<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateIdentity<'info>>,
+        old_user: Pubkey,
+    ) -> Result<()> {
+        identity::migrate_identity(ctx, old_user)
+    }
+
     pub fn escalate_report(
         ctx: Context<EscalateReport>,
         report_id: u64,
-        reason: String,
+        reason: EscalationReason,
+        description: String,
     ) -> Result<()> {
-        escalation::escalate_report(ctx, report_id, reason)
+        escalation::escalate_report(ctx, report_id, reason, description)
+    }
+
+    pub fn resolve_escalation(
+        ctx: Context<ResolveEscalation>,
+        report_id: u64,
+        resolution_details: String,
+    ) -> Result<()> {
+        escalation::resolve_escalation(ctx, report_id, resolution_details)
     }
 
     pub fn update_report_status(
@@ -81,8 +333,22 @@ pub mod civicaid_dao {
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
-    #[account(init, payer = authority, space = 8 + 32 + 1 + 8 + 8)]
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 1 + 8 + 8 + 8 + 8,
+        seeds = [b"state"],
+        bump
+    )]
     pub state: Account<'info, ProgramState>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Config::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -94,24 +360,8 @@ pub struct ProgramState {
     pub bump: u8,
     pub report_count: u64,
     pub total_rewards_distributed: u64,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum ReportCategory {
-    RoadIssue,
-    StreetLight,
-    PublicFacility,
-    EnvironmentalConcern,
-    Other,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum ReportStatus {
-    Submitted,
-    UnderReview,
-    Approved,
-    Resolved,
-    Rejected,
+    pub total_rewards_claimed: u64,
+    pub total_rewards_clawed_back: u64,
 }
 
 #[error_code]
@@ -124,4 +374,116 @@ pub enum ErrorCode {
     InsufficientFunds,
     #[msg("User not authorized")]
     Unauthorized,
+    #[msg("Input exceeds the maximum allowed length")]
+    InputTooLong,
+    #[msg("Insufficient reputation for this action")]
+    InsufficientReputation,
+    #[msg("Report is not in a valid status for this action")]
+    InvalidReportStatus,
+    #[msg("User has already voted on this report")]
+    AlreadyVoted,
+    #[msg("Account does not match the report submitter")]
+    InvalidSubmitter,
+    #[msg("Report has already been rewarded")]
+    AlreadyRewarded,
+    #[msg("Invalid token account owner")]
+    InvalidTokenAccount,
+    #[msg("Token account mint does not match the reward vault")]
+    InvalidMint,
+    #[msg("Reputation account does not belong to this user")]
+    UserMismatch,
+    #[msg("Report has no escalation to resolve")]
+    NoEscalationFound,
+    #[msg("Arithmetic overflow")]
+    ArithmeticOverflow,
+    #[msg("Invalid configuration parameters")]
+    InvalidConfig,
+    #[msg("Voting period for this report has ended")]
+    VotingClosed,
+    #[msg("Voting period for this report has not ended yet")]
+    VotingStillOpen,
+    #[msg("This report requires commit-reveal voting")]
+    CommitRevealRequired,
+    #[msg("Commit-reveal voting is not enabled for this report")]
+    CommitRevealNotEnabled,
+    #[msg("Report is not in its reveal window")]
+    NotInRevealWindow,
+    #[msg("Revealed vote does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("Vote is not in a valid phase for this action")]
+    InvalidVotePhase,
+    #[msg("New vote is the same as the current vote")]
+    VoteUnchanged,
+    #[msg("Report has not been finalized with an outcome")]
+    ReportNotFinalized,
+    #[msg("Remaining accounts are missing or do not match")]
+    InvalidRemainingAccounts,
+    #[msg("Reward ledger has no free grant slots")]
+    RewardLedgerFull,
+    #[msg("No vested rewards to claim")]
+    NothingToClaim,
+    #[msg("Cannot delegate voting weight to yourself")]
+    SelfDelegation,
+    #[msg("No delegation is set for this scope")]
+    NoDelegation,
+    #[msg("Voter is not the delegate for this delegation")]
+    NotDelegate,
+    #[msg("This report requires quadratic voting")]
+    QuadraticVotingRequired,
+    #[msg("Quadratic voting is not enabled for this report")]
+    QuadraticVotingNotEnabled,
+    #[msg("Vote intensity must be at least one")]
+    InvalidVoteIntensity,
+    #[msg("Not enough voice credits left this epoch")]
+    InsufficientVoiceCredits,
+    #[msg("Users cannot vote on their own reports")]
+    SelfVoting,
+    #[msg("Reputation account is too new for this action")]
+    AccountTooNew,
+    #[msg("Insufficient reputation to vote")]
+    InsufficientReputationForVoting,
+    #[msg("This action requires a valid personhood attestation")]
+    HumanVerificationRequired,
+    #[msg("Signer is not a registered verifier")]
+    NotVerifier,
+    #[msg("Attestation expiry must be in the future")]
+    InvalidAttestationExpiry,
+    #[msg("Attestation is not active")]
+    AttestationNotActive,
+    #[msg("Users cannot vouch for themselves")]
+    SelfVouch,
+    #[msg("Vouch stake is below the minimum or exceeds the voucher's reputation")]
+    InvalidVouchStake,
+    #[msg("Vouch has already been settled")]
+    VouchNotActive,
+    #[msg("Vouch stake is still locked")]
+    VouchStillLocked,
+    #[msg("Newcomer is below the slash threshold")]
+    NewcomerBelowSlashThreshold,
+    #[msg("Newcomer is not below the slash threshold")]
+    NewcomerAboveSlashThreshold,
+    #[msg("History entry is not a penalty that can be appealed")]
+    NotAppealable,
+    #[msg("Appeal window for this penalty has closed")]
+    AppealWindowClosed,
+    #[msg("Appeal has already been reviewed")]
+    AppealNotPending,
+    #[msg("Identity has been migrated to another wallet")]
+    IdentityMigrated,
+    #[msg("Migration needs the old wallet or enough guardians to sign")]
+    MigrationNotAuthorized,
+    #[msg("Guardians must be distinct and cover the threshold")]
+    InvalidGuardians,
+    #[msg("Reputation tier too low for this action")]
+    InsufficientTier,
+    #[msg("Report holds no submission bond")]
+    NoSubmissionBond,
+    #[msg("Only newcomers can be vouched for")]
+    NotNewcomer,
+    #[msg("Settle open vouches and reports before migrating")]
+    MigrationBlocked,
+    #[msg("Votes on an approved report settle once its fraud window has closed")]
+    FraudWindowOpen,
+    #[msg("Fraud window for this report has closed")]
+    FraudWindowClosed,
 }
\ No newline at end of file
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::ErrorCode;
+use crate::config::{Config, ConfigParams};
+use crate::submission::ReportCategory;
+use crate::tiers::{ReputationTier, TierChanged, TierParams};
+use crate::{ErrorCode, ProgramState};
 
 #[derive(Accounts)]
 #[instruction(user: Pubkey)]
@@ -11,43 +14,487 @@ pub struct UpdateReputation<'info> {
         constraint = user_reputation.user == user @ ErrorCode::UserMismatch
     )]
     pub user_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", user.as_ref()],
+        bump = user_history.bump
+    )]
+    pub user_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub state: Account<'info, ProgramState>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
     #[account(mut)]
     pub authority: Signer<'info>,
-    #[account(seeds = [b"authority".as_ref()], bump)]
-    pub program_authority: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct GetEffectiveReputation<'info> {
+    #[account(
+        seeds = [b"reputation", user.as_ref()],
+        bump,
+        constraint = user_reputation.user == user @ ErrorCode::UserMismatch
+    )]
+    pub user_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
 #[account]
 pub struct UserReputation {
     pub user: Pubkey,
     pub reputation_score: i64,
     pub reports_submitted: u64,
     pub reports_validated: u64,
+    /// Decay of the global score is measured from here. Decay worth less
+    /// than a point is left to accrue, so this can trail the last update.
     pub last_updated: i64,
+    pub created_at: i64,
+    /// Expiry of the user's attestation, mirrored from their `Attestation`
+    /// PDA so eligibility checks need no extra account. Zero when none.
+    pub attested_until: i64,
+    /// Reputation earned from reports in each category, indexed by
+    /// `ReportCategory::index`. `None` until the first change in that
+    /// category; the global score is used until then.
+    pub category_scores: [Option<i64>; ReportCategory::COUNT],
+    /// Counterpart of `last_updated` for each category score.
+    pub category_updated: [i64; ReportCategory::COUNT],
+    /// Set when the user moved to a new wallet; the account is kept as a
+    /// tombstone so it cannot be initialized again.
+    pub migrated_to: Option<Pubkey>,
+    /// Tier as of the last score change, kept to detect tier changes.
+    pub tier: ReputationTier,
+    /// Vouches this user gave that are still active. Migration waits for
+    /// them to settle, since settlement is keyed to this wallet.
+    pub active_vouches: u32,
+    /// Reports this user submitted that are still open, see
+    /// `Report::is_open`. Migration waits for them for the same reason.
+    pub open_reports: u32,
+}
+
+impl UserReputation {
+    pub const LEN: usize = 32 // user
+        + 8 // reputation_score
+        + 8 // reports_submitted
+        + 8 // reports_validated
+        + 8 // last_updated
+        + 8 // created_at
+        + 8 // attested_until
+        + (1 + 8) * ReportCategory::COUNT // category_scores
+        + 8 * ReportCategory::COUNT // category_updated
+        + (1 + 32) // migrated_to
+        + 1 // tier
+        + 4 // active_vouches
+        + 4; // open_reports
+
+    pub fn is_active(&self) -> Result<()> {
+        if self.migrated_to.is_some() {
+            return Err(ErrorCode::IdentityMigrated.into());
+        }
+        Ok(())
+    }
+
+    pub fn report_closed(&mut self) {
+        self.open_reports = self.open_reports.saturating_sub(1);
+    }
+
+    pub fn has_valid_attestation(&self, now: i64) -> bool {
+        self.attested_until > now
+    }
+
+    /// Score after decaying toward zero since `last_updated`.
+    pub fn effective_score(&self, config: &ConfigParams, now: i64) -> i64 {
+        decayed_score(
+            self.reputation_score,
+            now.saturating_sub(self.last_updated),
+            config.reputation_half_life_seconds,
+        )
+    }
+
+    /// Folds pending decay into the stored scores and tier. Called before the
+    /// score of a writable account is read or changed.
+    pub fn apply_decay(&mut self, config: &ConfigParams, now: i64) {
+        let half_life = config.reputation_half_life_seconds;
+        decay_since(&mut self.reputation_score, &mut self.last_updated, now, half_life);
+        for (score, updated) in self.category_scores.iter_mut().zip(self.category_updated.iter_mut()) {
+            if let Some(score) = score {
+                decay_since(score, updated, now, half_life);
+            }
+        }
+        self.refresh_tier(&config.tiers);
+    }
+
+    /// Sets the global score after a change. The caller refreshes the tier.
+    pub fn set_score(&mut self, score: i64, now: i64) {
+        rebase_decay(&mut self.last_updated, self.reputation_score, score, now);
+        self.reputation_score = score;
+    }
+
+    /// Score that applies to reports in `category`.
+    pub fn score_for(&self, category: &ReportCategory) -> i64 {
+        self.category_scores[category.index()].unwrap_or(self.reputation_score)
+    }
+
+    /// Reputation-based vote weight in `category`, capped by the tier.
+    pub fn vote_weight(&self, category: &ReportCategory, config: &ConfigParams) -> u64 {
+        let tier = config.tiers.tier_for(self.reputation_score);
+        config
+            .voting
+            .vote_weight(self.score_for(category))
+            .min(config.tiers.permissions(tier).max_vote_weight)
+    }
+
+    /// Recomputes the stored tier after the score changed, emitting an
+    /// event when it moved.
+    pub fn refresh_tier(&mut self, params: &TierParams) {
+        let new_tier = params.tier_for(self.reputation_score);
+        if new_tier == self.tier {
+            return;
+        }
+
+        // Emit an event for the tier change
+        emit!(TierChanged {
+            user: self.user,
+            old_tier: self.tier,
+            new_tier,
+            reputation_score: self.reputation_score,
+        });
+        self.tier = new_tier;
+    }
+
+    /// Applies a report-related change to the category score and returns the
+    /// change actually applied. The caller then applies the same change to
+    /// the global score. A category without a score of its own starts from
+    /// the global score it was using so far.
+    pub fn change_category_score(&mut self, category: &ReportCategory, change: i64, config: &ConfigParams, now: i64) -> i64 {
+        let index = category.index();
+        let old_score = match self.category_scores[index] {
+            Some(score) => score,
+            None => {
+                self.category_updated[index] = self.last_updated;
+                self.reputation_score
+            }
+        };
+        let new_score = old_score
+            .saturating_add(change)
+            .clamp(config.min_reputation, config.max_reputation);
+        rebase_decay(&mut self.category_updated[index], old_score, new_score, now);
+        self.category_scores[index] = Some(new_score);
+        new_score - old_score
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReputationChangeReason {
+    #[default]
+    ManualAdjustment,
+    VoteReceived,
+    VoteWithdrawn,
+    ReportApproved,
+    ReportRejected,
+    VoteWon,
+    VoteLost,
+    UnrevealedVote,
+    FraudPenalty,
+    VouchStake,
+    VouchBonus,
+    VouchReleased,
+    AppealUpheld,
+}
+
+impl ReputationChangeReason {
+    /// Penalties imposed by a decision rather than ordinary voting outcomes
+    /// or voluntary stakes; only these can be appealed.
+    pub fn is_appealable(&self) -> bool {
+        matches!(
+            self,
+            ReputationChangeReason::ManualAdjustment
+                | ReputationChangeReason::ReportRejected
+                | ReputationChangeReason::UnrevealedVote
+                | ReputationChangeReason::FraudPenalty
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ReputationChange {
+    /// Change actually applied to the global score, after clamping.
+    pub delta: i64,
+    pub reason: ReputationChangeReason,
+    pub report_id: Option<u64>,
+    /// Signer that caused the change; `Pubkey::default()` for permissionless
+    /// cranks.
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    /// Set when an upheld appeal reversed this change.
+    pub reversed: bool,
+    /// Category score changed along with the global score, if any, and the
+    /// change actually applied to it.
+    pub category: Option<ReportCategory>,
+    pub category_delta: i64,
+}
+
+impl ReputationChange {
+    pub const LEN: usize = 8 // delta
+        + 1 // reason
+        + 1 + 8 // report_id
+        + 32 // actor
+        + 8 // timestamp
+        + 1 // reversed
+        + 1 + 1 // category
+        + 8; // category_delta
+
+    /// Notes the category score change made together with this one.
+    pub fn set_category_change(&mut self, category: ReportCategory, delta: i64) {
+        self.category = Some(category);
+        self.category_delta = delta;
+    }
+}
+
+/// Ring buffer of a user's most recent reputation changes. Decay is not
+/// recorded since it is applied lazily.
+#[account]
+pub struct ReputationHistory {
+    pub user: Pubkey,
+    pub bump: u8,
+    /// Wallet the history was first recorded for. Migration carries it over,
+    /// and appeals are keyed by it so an entry is appealed only once.
+    pub origin: Pubkey,
+    /// Total changes ever recorded; the next entry goes to
+    /// `total_changes % MAX_ENTRIES`.
+    pub total_changes: u64,
+    pub entries: [ReputationChange; ReputationHistory::MAX_ENTRIES],
+}
+
+impl ReputationHistory {
+    pub const MAX_ENTRIES: usize = 32;
+
+    pub const LEN: usize = 32 // user
+        + 1 // bump
+        + 32 // origin
+        + 8 // total_changes
+        + ReputationChange::LEN * Self::MAX_ENTRIES;
+
+    /// Records a change, overwriting the oldest entry once the buffer is full.
+    /// Zero deltas are skipped.
+    /// Appends a change to the global score and returns its entry. Zero
+    /// deltas are not recorded.
+    pub fn record(
+        &mut self,
+        delta: i64,
+        reason: ReputationChangeReason,
+        report_id: Option<u64>,
+        actor: Pubkey,
+        now: i64,
+    ) -> Option<&mut ReputationChange> {
+        if delta == 0 {
+            return None;
+        }
+        let slot = (self.total_changes % Self::MAX_ENTRIES as u64) as usize;
+        self.entries[slot] = ReputationChange {
+            delta,
+            reason,
+            report_id,
+            actor,
+            timestamp: now,
+            ..ReputationChange::default()
+        };
+        self.total_changes += 1;
+        Some(&mut self.entries[slot])
+    }
+
+    /// Entry with sequence number `index`, if it has not been overwritten.
+    pub fn entry(&self, index: u64) -> Option<&ReputationChange> {
+        self.retains(index)
+            .then(|| &self.entries[(index % Self::MAX_ENTRIES as u64) as usize])
+    }
+
+    pub fn entry_mut(&mut self, index: u64) -> Option<&mut ReputationChange> {
+        if !self.retains(index) {
+            return None;
+        }
+        Some(&mut self.entries[(index % Self::MAX_ENTRIES as u64) as usize])
+    }
+
+    fn retains(&self, index: u64) -> bool {
+        index < self.total_changes && self.total_changes - index <= Self::MAX_ENTRIES as u64
+    }
+}
+
+// Halves the score every `half_life` seconds, interpolating linearly within
+// a half-life. Division truncates, so negative scores also move toward zero.
+// A non-positive half-life disables decay.
+// Decays `score` for the time since `updated` and moves `updated` forward by
+// the time the decay accounts for. The rest would take off less than a point
+// and keeps accruing rather than being rounded away on every update.
+fn decay_since(score: &mut i64, updated: &mut i64, now: i64, half_life: i64) {
+    let accounted = decay_accounted(*score, now.saturating_sub(*updated), half_life);
+    *score = decayed_score(*score, accounted, half_life);
+    *updated = updated.saturating_add(accounted);
+}
+
+// Part of `elapsed` that `decayed_score` turns into whole points
+fn decay_accounted(score: i64, elapsed: i64, half_life: i64) -> i64 {
+    if half_life <= 0 || elapsed <= 0 {
+        return elapsed.max(0);
+    }
+    let halvings = elapsed / half_life;
+    if halvings >= 63 {
+        return elapsed;
+    }
+    let halved = (score as i128 / (1i128 << halvings)).abs();
+    if halved == 0 {
+        return elapsed;
+    }
+    let partial = halved * (elapsed % half_life) as i128 / (2 * half_life as i128);
+    // Shortest time into the half-life that takes off as many points
+    let into_half_life = (partial * 2 * half_life as i128 + halved - 1) / halved;
+    halvings * half_life + into_half_life as i64
+}
+
+// When a score changes, scales the time since `updated` so the decay still
+// pending on the old score stays the same number of points on the new one.
+// Otherwise that time would count in full against the change.
+fn rebase_decay(updated: &mut i64, old_score: i64, new_score: i64, now: i64) {
+    let pending = now.saturating_sub(*updated).max(0) as i128;
+    let rebased = if old_score.signum() == new_score.signum() && new_score != 0 {
+        pending * old_score.unsigned_abs() as i128 / new_score.unsigned_abs() as i128
+    } else {
+        0
+    };
+    *updated = now.saturating_sub(rebased.min(i64::MAX as i128) as i64);
+}
+
+fn decayed_score(score: i64, elapsed: i64, half_life: i64) -> i64 {
+    if half_life <= 0 || elapsed <= 0 {
+        return score;
+    }
+    let halvings = elapsed / half_life;
+    if halvings >= 63 {
+        return 0;
+    }
+    let halved = score as i128 / (1i128 << halvings);
+    let partial = halved * (elapsed % half_life) as i128 / (2 * half_life as i128);
+    (halved - partial) as i64
+}
+
+/// Sybil-resistance rules for voters and submitters. Self-voting is always
+/// refused; the remaining checks are configurable.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct EligibilityParams {
+    /// Minimum time since the reputation account was created.
+    pub min_account_age_seconds: i64,
+    pub minimum_reputation_for_voting: i64,
+    pub require_verified_human_for_voting: bool,
+    pub require_verified_human_for_submission: bool,
+    pub require_verified_human_for_escalation: bool,
+}
+
+impl EligibilityParams {
+    pub const LEN: usize = 8 // min_account_age_seconds
+        + 8 // minimum_reputation_for_voting
+        + 1 // require_verified_human_for_voting
+        + 1 // require_verified_human_for_submission
+        + 1; // require_verified_human_for_escalation
+
+    pub fn validate(&self) -> Result<()> {
+        if self.min_account_age_seconds < 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        Ok(())
+    }
+
+    pub fn check_voter(&self, voter: &UserReputation, submitter: &Pubkey, now: i64) -> Result<()> {
+        voter.is_active()?;
+        if voter.user == *submitter {
+            return Err(ErrorCode::SelfVoting.into());
+        }
+        self.check_account_age(voter, now)?;
+        if voter.reputation_score < self.minimum_reputation_for_voting {
+            return Err(ErrorCode::InsufficientReputationForVoting.into());
+        }
+        if self.require_verified_human_for_voting && !voter.has_valid_attestation(now) {
+            return Err(ErrorCode::HumanVerificationRequired.into());
+        }
+        Ok(())
+    }
+
+    pub fn check_submitter(&self, submitter: &UserReputation, now: i64) -> Result<()> {
+        submitter.is_active()?;
+        self.check_account_age(submitter, now)?;
+        if self.require_verified_human_for_submission && !submitter.has_valid_attestation(now) {
+            return Err(ErrorCode::HumanVerificationRequired.into());
+        }
+        Ok(())
+    }
+
+    pub fn check_escalator(&self, user: &UserReputation, now: i64) -> Result<()> {
+        user.is_active()?;
+        if self.require_verified_human_for_escalation && !user.has_valid_attestation(now) {
+            return Err(ErrorCode::HumanVerificationRequired.into());
+        }
+        Ok(())
+    }
+
+    fn check_account_age(&self, user: &UserReputation, now: i64) -> Result<()> {
+        if now.saturating_sub(user.created_at) < self.min_account_age_seconds {
+            return Err(ErrorCode::AccountTooNew.into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for EligibilityParams {
+    fn default() -> Self {
+        Self {
+            min_account_age_seconds: 24 * 60 * 60,
+            minimum_reputation_for_voting: -100,
+            require_verified_human_for_voting: false,
+            require_verified_human_for_submission: false,
+            require_verified_human_for_escalation: false,
+        }
+    }
 }
 
 pub fn update_reputation(ctx: Context<UpdateReputation>, user: Pubkey, change: i64) -> Result<()> {
     let user_reputation = &mut ctx.accounts.user_reputation;
-    let authority = &ctx.accounts.authority;
-    let program_authority = &ctx.accounts.program_authority;
+    let config = &ctx.accounts.config.params;
+    let now = Clock::get()?.unix_timestamp;
 
-    // Ensure only the program authority can update reputation
-    if authority.key() != program_authority.key() {
-        return Err(ErrorCode::Unauthorized.into());
-    }
+    user_reputation.apply_decay(config, now);
 
     // Update reputation score
-    let new_score = user_reputation.reputation_score.saturating_add(change);
-    user_reputation.reputation_score = new_score.clamp(MIN_REPUTATION, MAX_REPUTATION);
+    let old_score = user_reputation.reputation_score;
+    let new_score = old_score
+        .saturating_add(change)
+        .clamp(config.min_reputation, config.max_reputation);
+    user_reputation.set_score(new_score, now);
+    user_reputation.refresh_tier(&config.tiers);
 
-    // Update last updated timestamp
-    user_reputation.last_updated = Clock::get()?.unix_timestamp;
+    ctx.accounts.user_history.record(
+        new_score - old_score,
+        ReputationChangeReason::ManualAdjustment,
+        None,
+        ctx.accounts.authority.key(),
+        now,
+    );
 
     // Emit an event for the reputation update
     emit!(ReputationUpdated {
         user,
-        old_score: user_reputation.reputation_score,
+        old_score,
         new_score,
         change,
     });
@@ -58,17 +505,15 @@ pub fn update_reputation(ctx: Context<UpdateReputation>, user: Pubkey, change: i
     Ok(())
 }
 
-pub fn get_user_reputation(user: &Pubkey) -> Result<i64> {
-    let (user_reputation_pubkey, _) = Pubkey::find_program_address(
-        &[b"reputation", user.as_ref()],
-        &crate::ID,
-    );
-
-    let user_reputation = UserReputation::try_from_account_info(&user_reputation_pubkey.to_account_info())?;
-    Ok(user_reputation.reputation_score)
+pub fn get_effective_reputation(ctx: Context<GetEffectiveReputation>, _user: Pubkey) -> Result<i64> {
+    let now = Clock::get()?.unix_timestamp;
+    Ok(ctx.accounts.user_reputation.effective_score(&ctx.accounts.config.params, now))
 }
 
-pub fn initialize_user_reputation(ctx: Context<InitializeUserReputation>, user: Pubkey) -> Result<()> {
+/// Opens the signing user's reputation and history accounts. Rent is paid by
+/// `payer`, which is the user themselves or a sponsor covering onboarding.
+pub fn initialize_user_reputation(ctx: Context<InitializeUserReputation>) -> Result<()> {
+    let user = ctx.accounts.user.key();
     let user_reputation = &mut ctx.accounts.user_reputation;
     
     user_reputation.user = user;
@@ -76,26 +521,64 @@ pub fn initialize_user_reputation(ctx: Context<InitializeUserReputation>, user:
     user_reputation.reports_submitted = 0;
     user_reputation.reports_validated = 0;
     user_reputation.last_updated = Clock::get()?.unix_timestamp;
+    user_reputation.created_at = user_reputation.last_updated;
+    user_reputation.attested_until = 0;
+    user_reputation.category_scores = [None; ReportCategory::COUNT];
+    user_reputation.category_updated = [0; ReportCategory::COUNT];
+    user_reputation.migrated_to = None;
+    user_reputation.tier = ReputationTier::Newcomer;
+    user_reputation.active_vouches = 0;
+    user_reputation.open_reports = 0;
+
+    let user_history = &mut ctx.accounts.user_history;
+    user_history.user = user;
+    user_history.bump = *ctx.bumps.get("user_history").unwrap();
+    user_history.origin = user;
+    user_history.total_changes = 0;
+
+    // Emit an event for the new account
+    emit!(UserReputationInitialized {
+        user,
+        payer: ctx.accounts.payer.key(),
+        initial_score: INITIAL_REPUTATION,
+    });
 
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(user: Pubkey)]
 pub struct InitializeUserReputation<'info> {
+    pub user: Signer<'info>,
     #[account(
         init,
-        payer = authority,
-        space = 8 + 32 + 8 + 8 + 8 + 8,
-        seeds = [b"reputation", user.as_ref()],
+        payer = payer,
+        space = 8 + UserReputation::LEN,
+        seeds = [b"reputation", user.key().as_ref()],
         bump
     )]
     pub user_reputation: Account<'info, UserReputation>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ReputationHistory::LEN,
+        seeds = [b"reputation_history", user.key().as_ref()],
+        bump
+    )]
+    pub user_history: Box<Account<'info, ReputationHistory>>,
+    /// Pays rent for both accounts; may be the user or a sponsor.
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+// Event emitted when a user opens their reputation account
+#[event]
+pub struct UserReputationInitialized {
+    pub user: Pubkey,
+    pub payer: Pubkey,
+    pub initial_score: i64,
+}
+
 // Event emitted when a user's reputation is updated
 #[event]
 pub struct ReputationUpdated {
@@ -106,6 +589,118 @@ pub struct ReputationUpdated {
 }
 
 // Constants
-const MIN_REPUTATION: i64 = -1000;
-const MAX_REPUTATION: i64 = 1000;
-const INITIAL_REPUTATION: i64 = 0;
\ No newline at end of file
+const INITIAL_REPUTATION: i64 = 0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALF_LIFE: i64 = 1_000;
+
+    #[test]
+    fn decay_halves_score_after_one_half_life() {
+        assert_eq!(decayed_score(800, HALF_LIFE, HALF_LIFE), 400);
+        assert_eq!(decayed_score(800, 2 * HALF_LIFE, HALF_LIFE), 200);
+    }
+
+    #[test]
+    fn decay_interpolates_within_a_half_life() {
+        assert_eq!(decayed_score(800, HALF_LIFE / 2, HALF_LIFE), 600);
+        assert_eq!(decayed_score(800, HALF_LIFE + HALF_LIFE / 2, HALF_LIFE), 300);
+    }
+
+    #[test]
+    fn decay_reaches_zero_after_63_halvings() {
+        assert_eq!(decayed_score(i64::MAX, 62 * HALF_LIFE, HALF_LIFE), 1);
+        assert_eq!(decayed_score(i64::MAX, 63 * HALF_LIFE, HALF_LIFE), 0);
+        assert_eq!(decayed_score(i64::MIN, 63 * HALF_LIFE, HALF_LIFE), 0);
+        assert_eq!(decayed_score(i64::MAX, i64::MAX, HALF_LIFE), 0);
+    }
+
+    #[test]
+    fn decay_truncates_negative_scores_toward_zero() {
+        assert_eq!(decayed_score(-800, HALF_LIFE, HALF_LIFE), -400);
+        assert_eq!(decayed_score(-800, HALF_LIFE / 2, HALF_LIFE), -600);
+        assert_eq!(decayed_score(-3, HALF_LIFE, HALF_LIFE), -1);
+        assert_eq!(decayed_score(-1, HALF_LIFE, HALF_LIFE), 0);
+    }
+
+    fn reputation_with(score: i64, category_score: i64, config: &ConfigParams) -> UserReputation {
+        let mut category_scores = [None; ReportCategory::COUNT];
+        category_scores[0] = Some(category_score);
+        UserReputation {
+            user: Pubkey::default(),
+            reputation_score: score,
+            reports_submitted: 0,
+            reports_validated: 0,
+            last_updated: 0,
+            created_at: 0,
+            attested_until: 0,
+            category_scores,
+            category_updated: [0; ReportCategory::COUNT],
+            migrated_to: None,
+            tier: config.tiers.tier_for(score),
+            active_vouches: 0,
+            open_reports: 0,
+        }
+    }
+
+    fn config_with_half_life(half_life: i64) -> ConfigParams {
+        ConfigParams {
+            reputation_half_life_seconds: half_life,
+            ..ConfigParams::default()
+        }
+    }
+
+    #[test]
+    fn frequent_decay_keeps_up_with_one_long_gap() {
+        let config = config_with_half_life(HALF_LIFE);
+        let mut touched = reputation_with(100, 40, &config);
+        let mut idle = reputation_with(100, 40, &config);
+
+        // Each touch alone is worth less than a point of decay
+        for now in 1..=HALF_LIFE / 10 {
+            touched.apply_decay(&config, now);
+        }
+        idle.apply_decay(&config, HALF_LIFE / 10);
+
+        assert_eq!(idle.reputation_score, 95);
+        assert_eq!(idle.category_scores[0], Some(38));
+        assert!((touched.reputation_score - idle.reputation_score).abs() <= 1);
+        assert!((touched.category_scores[0].unwrap() - idle.category_scores[0].unwrap()).abs() <= 1);
+    }
+
+    #[test]
+    fn decay_below_a_point_is_carried_over() {
+        let config = config_with_half_life(HALF_LIFE);
+        let mut reputation = reputation_with(100, 40, &config);
+
+        // 15 seconds decay 0.75 points, and 5 more complete the first one
+        reputation.apply_decay(&config, 15);
+        assert_eq!(reputation.reputation_score, 100);
+        assert_eq!(reputation.last_updated, 0);
+        reputation.apply_decay(&config, 25);
+        assert_eq!(reputation.reputation_score, 99);
+        assert_eq!(reputation.last_updated, 20);
+    }
+
+    #[test]
+    fn score_change_keeps_pending_decay_in_points() {
+        let config = config_with_half_life(HALF_LIFE);
+        let mut reputation = reputation_with(100, 40, &config);
+        reputation.apply_decay(&config, 10);
+
+        // Half a point is pending; on twice the score it takes half the time
+        reputation.set_score(200, 10);
+        assert_eq!(reputation.last_updated, 5);
+        reputation.change_category_score(&ReportCategory::RoadIssue, -60, &config, 10);
+        assert_eq!(reputation.category_updated[0], 10);
+    }
+
+    #[test]
+    fn decay_is_skipped_without_elapsed_time_or_half_life() {
+        assert_eq!(decayed_score(800, 0, HALF_LIFE), 800);
+        assert_eq!(decayed_score(800, -HALF_LIFE, HALF_LIFE), 800);
+        assert_eq!(decayed_score(800, HALF_LIFE, 0), 800);
+    }
+}
@@ -1,82 +1,759 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::submission::Report;
-use crate::ErrorCode;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::config::{Config, ConfigParams};
+use crate::reputation::{ReputationChangeReason, ReputationHistory, UserReputation};
+use crate::submission::{transition_report_status, Report, ReportCategory, ReportStatus};
+use crate::tiers::check_moderator_tier;
+use crate::voting::{side_weights, Vote, VoteType};
+use crate::{ErrorCode, ProgramState};
 
 #[derive(Accounts)]
 #[instruction(report_id: u64)]
 pub struct DistributeRewards<'info> {
     #[account(
         mut,
-        constraint = report.key() == report_id @ ErrorCode::ReportNotFound,
-        constraint = report.submitter == submitter.key() @ ErrorCode::InvalidSubmitter,
-        constraint = !report.rewarded @ ErrorCode::AlreadyRewarded
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
+        constraint = report.status == ReportStatus::Approved @ ErrorCode::InvalidReportStatus,
+        constraint = !report.reward_distributed @ ErrorCode::AlreadyRewarded
     )]
     pub report: Account<'info, Report>,
+    /// Anyone may crank the payout; the cranker only pays for the submitter's
+    /// ledger if it does not exist yet.
     #[account(mut)]
-    pub submitter: Signer<'info>,
+    pub cranker: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + RewardLedger::LEN,
+        seeds = [b"reward_ledger", report.submitter.as_ref()],
+        bump
+    )]
+    pub reward_ledger: Account<'info, RewardLedger>,
+    #[account(seeds = [b"reward_vault"], bump)]
+    pub reward_vault: Account<'info, TokenAccount>,
     #[account(
         mut,
-        constraint = submitter_token_account.owner == submitter.key() @ ErrorCode::InvalidTokenAccount
+        seeds = [b"state"],
+        bump = state.bump
     )]
-    pub submitter_token_account: Account<'info, TokenAccount>,
+    pub state: Account<'info, ProgramState>,
     #[account(
         mut,
-        constraint = reward_vault.mint == submitter_token_account.mint @ ErrorCode::InvalidMint
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct ClaimValidatorReward<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump
     )]
+    pub report: Account<'info, Report>,
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [b"vote", report.key().as_ref(), voter.key().as_ref()],
+        bump = vote.bump
+    )]
+    pub vote: Account<'info, Vote>,
+    /// CHECK: gets the vote account's rent back; the delegate who paid it
+    /// for a delegated vote, otherwise the voter
+    #[account(mut, address = vote.cast_by.unwrap_or_else(|| voter.key()))]
+    pub rent_recipient: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + RewardLedger::LEN,
+        seeds = [b"reward_ledger", voter.key().as_ref()],
+        bump
+    )]
+    pub reward_ledger: Account<'info, RewardLedger>,
+    #[account(seeds = [b"reward_vault"], bump)]
     pub reward_vault: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    /// Claims can be cranked on the owner's behalf, like distributions.
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    /// CHECK: only the ledger owner's address is used, to derive their ATA
+    #[account(address = reward_ledger.owner @ ErrorCode::UserMismatch)]
+    pub owner: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"reward_ledger", owner.key().as_ref()],
+        bump = reward_ledger.bump
+    )]
+    pub reward_ledger: Account<'info, RewardLedger>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = mint,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"reward_vault"],
+        bump,
+        constraint = reward_vault.mint == mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+    /// CHECK: PDA that owns the vault and signs the transfer
     #[account(seeds = [b"authority".as_ref()], bump)]
     pub program_authority: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Remaining accounts start with the decider's reputation when they act on
+/// their tier. If the submitter migrated, a writable `(reputation,
+/// reputation_history, reward_ledger)` triple follows for each wallet the
+/// identity moved to since, in order.
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct FlagFraudulentReport<'info> {
+    #[account(
+        mut,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump
+    )]
+    pub report: Account<'info, Report>,
+    /// CHECK: only deserialized when the report was rewarded; unrewarded
+    /// submitters may not have a ledger yet. The seeds pin the address.
+    #[account(
+        mut,
+        seeds = [b"reward_ledger", report.submitter.as_ref()],
+        bump
+    )]
+    pub reward_ledger: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", report.submitter.as_ref()],
+        bump = submitter_history.bump
+    )]
+    pub submitter_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub decided_by: Signer<'info>,
+}
+
+#[account]
+pub struct RewardLedger {
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub total_credited: u64,
+    pub total_claimed: u64,
+    /// Validator shares, credited without vesting so that frequent small
+    /// credits do not use up grant slots.
+    pub liquid: u64,
+    pub grants: Vec<RewardGrant>,
+}
+
+impl RewardLedger {
+    pub const MAX_GRANTS: usize = 16;
+
+    pub const LEN: usize = 32 // owner
+        + 1 // bump
+        + 8 // total_credited
+        + 8 // total_claimed
+        + 8 // liquid
+        + 4 + Self::MAX_GRANTS * RewardGrant::LEN;
+
+    /// Total amount that has vested but not yet been claimed.
+    pub fn claimable(&self, now: i64) -> u64 {
+        self.liquid + self.grants.iter().map(|grant| grant.claimable(now)).sum::<u64>()
+    }
+
+    // Fully claimed grants carry no further obligations, so they are dropped
+    // to make room for new credits.
+    fn prune_settled(&mut self) {
+        self.grants.retain(|grant| grant.claimed < grant.amount);
+    }
+
+    /// Moves every outstanding grant from `other` into this ledger, leaving
+    /// `other` empty. Used when a user migrates to a new wallet.
+    pub fn take_grants_from(&mut self, other: &mut RewardLedger) -> Result<()> {
+        self.prune_settled();
+        other.prune_settled();
+        if self.grants.len() + other.grants.len() > Self::MAX_GRANTS {
+            return Err(ErrorCode::RewardLedgerFull.into());
+        }
+        self.total_credited = self
+            .total_credited
+            .checked_add(other.total_credited)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.total_claimed = self
+            .total_claimed
+            .checked_add(other.total_claimed)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.liquid = self
+            .liquid
+            .checked_add(other.liquid)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.grants.append(&mut other.grants);
+        other.liquid = 0;
+        other.total_credited = 0;
+        other.total_claimed = 0;
+        Ok(())
+    }
+}
+
+/// A single report's reward, vesting linearly from `start` to `end` with
+/// nothing claimable before `cliff`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct RewardGrant {
+    pub report_id: u64,
+    pub amount: u64,
+    pub claimed: u64,
+    pub start: i64,
+    pub cliff: i64,
+    pub end: i64,
+}
+
+impl RewardGrant {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8;
+
+    pub fn vested(&self, now: i64) -> u64 {
+        if now < self.cliff {
+            return 0;
+        }
+        if now >= self.end {
+            return self.amount;
+        }
+        let elapsed = (now - self.start) as u128;
+        let duration = (self.end - self.start) as u128;
+        (self.amount as u128 * elapsed / duration) as u64
+    }
+
+    pub fn claimable(&self, now: i64) -> u64 {
+        self.vested(now).saturating_sub(self.claimed)
+    }
+
+    /// Cancels the unvested part of the grant and freezes vesting at `now`.
+    /// Returns the amount removed.
+    pub fn claw_back(&mut self, now: i64) -> u64 {
+        let vested = self.vested(now);
+        let clawed_back = self.amount - vested;
+        self.amount = vested;
+        self.cliff = self.cliff.min(now);
+        self.end = self.end.min(now);
+        clawed_back
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct QuoteReward<'info> {
+    #[account(
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump
+    )]
+    pub report: Account<'info, Report>,
+    #[account(
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReputationBonusTier {
+    pub min_reputation: i64,
+    pub bonus_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct RewardParams {
+    /// Base reward per `ReportCategory`, indexed by `ReportCategory::index`.
+    pub category_base_amounts: [u64; 5],
+    /// Bonus per net vote above the approval threshold.
+    pub vote_margin_bonus_bps: u16,
+    pub max_vote_margin_bonus_bps: u16,
+    /// Submitter bonus tiers, ordered by ascending `min_reputation`.
+    pub reputation_bonus_tiers: [ReputationBonusTier; 3],
+    pub max_reward_per_report: u64,
+    /// Seconds after crediting before any of a reward can be claimed.
+    pub vesting_cliff_seconds: i64,
+    /// Seconds over which a reward vests linearly; zero vests immediately.
+    pub vesting_duration_seconds: i64,
+    /// Tokens per finalized report shared among voters on the winning side,
+    /// in proportion to their vote weight.
+    pub validator_reward_pool: u64,
+}
+
+impl RewardParams {
+    pub const LEN: usize = 8 * ReportCategory::COUNT // category_base_amounts
+        + 2 // vote_margin_bonus_bps
+        + 2 // max_vote_margin_bonus_bps
+        + (8 + 2) * 3 // reputation_bonus_tiers
+        + 8 // max_reward_per_report
+        + 8 // vesting_cliff_seconds
+        + 8 // vesting_duration_seconds
+        + 8; // validator_reward_pool
+
+    pub fn validate(&self) -> Result<()> {
+        if self.max_reward_per_report == 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        if self.vote_margin_bonus_bps > self.max_vote_margin_bonus_bps {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        let tiers_sorted = self
+            .reputation_bonus_tiers
+            .windows(2)
+            .all(|pair| pair[0].min_reputation < pair[1].min_reputation);
+        if !tiers_sorted {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        if self.vesting_cliff_seconds < 0 || self.vesting_duration_seconds < self.vesting_cliff_seconds {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for RewardParams {
+    fn default() -> Self {
+        Self {
+            category_base_amounts: [100, 80, 100, 120, 50],
+            vote_margin_bonus_bps: 500,
+            max_vote_margin_bonus_bps: 5_000,
+            reputation_bonus_tiers: [
+                ReputationBonusTier { min_reputation: 50, bonus_bps: 500 },
+                ReputationBonusTier { min_reputation: 200, bonus_bps: 1_500 },
+                ReputationBonusTier { min_reputation: 500, bonus_bps: 3_000 },
+            ],
+            max_reward_per_report: 500,
+            vesting_cliff_seconds: 7 * 24 * 60 * 60,
+            vesting_duration_seconds: 30 * 24 * 60 * 60,
+            validator_reward_pool: 50,
+        }
+    }
 }
 
 pub fn distribute_rewards(ctx: Context<DistributeRewards>, report_id: u64) -> Result<()> {
     let report = &mut ctx.accounts.report;
-    let submitter = &ctx.accounts.submitter;
-    let submitter_token_account = &ctx.accounts.submitter_token_account;
+    let state = &mut ctx.accounts.state;
+    let reward_ledger = &mut ctx.accounts.reward_ledger;
     let reward_vault = &ctx.accounts.reward_vault;
-    let token_program = &ctx.accounts.token_program;
-    let program_authority = &ctx.accounts.program_authority;
+    let params = &ctx.accounts.config.params.rewards;
 
     // Calculate reward amount based on report quality or fixed amount
-    let reward_amount = calculate_reward_amount(report)?;
+    let submitter_score = ctx
+        .accounts
+        .submitter_reputation
+        .effective_score(&ctx.accounts.config.params, Clock::get()?.unix_timestamp);
+    let reward_amount = calculate_reward_amount(report, submitter_score, &ctx.accounts.config.params)?;
+
+    if reward_ledger.owner == Pubkey::default() {
+        reward_ledger.owner = report.submitter;
+        reward_ledger.bump = *ctx.bumps.get("reward_ledger").unwrap();
+    }
+
+    // Credit the reward to the submitter's ledger on the configured schedule
+    let grant = credit_reward(
+        reward_ledger,
+        state,
+        reward_vault.amount,
+        report_id,
+        reward_amount,
+        params,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // Mark the report as rewarded, which closes it
+    report.reward_distributed = true;
+    ctx.accounts.submitter_reputation.report_closed();
+
+    // Emit an event for the reward distribution
+    emit!(RewardDistributed {
+        report_id,
+        submitter: report.submitter,
+        amount: reward_amount,
+        vesting_cliff: grant.cliff,
+        vesting_end: grant.end,
+    });
+
+    msg!("Credited {} tokens to submitter: {}", reward_amount, report.submitter);
+
+    Ok(())
+}
+
+pub fn claim_validator_reward(ctx: Context<ClaimValidatorReward>, report_id: u64) -> Result<()> {
+    let vote = &ctx.accounts.vote;
+    let reward_ledger = &mut ctx.accounts.reward_ledger;
+    let state = &mut ctx.accounts.state;
+    let voter = &ctx.accounts.voter;
+
+    // Only the winning side holds shares
+    let on_winning_side = ctx.accounts.report.winning_vote.as_ref() == Some(&vote.vote_type);
+    if !vote.settled || vote.reward_share == 0 || !on_winning_side {
+        return Err(ErrorCode::NothingToClaim.into());
+    }
+
+    if reward_ledger.owner == Pubkey::default() {
+        reward_ledger.owner = voter.key();
+        reward_ledger.bump = *ctx.bumps.get("reward_ledger").unwrap();
+    }
+
+    // Shares are small and frequent, so they skip vesting and are pooled
+    // outside the grant slots; the vote account is closed
+    reserve_reward(state, ctx.accounts.reward_vault.amount, vote.reward_share)?;
+    reward_ledger.liquid = reward_ledger
+        .liquid
+        .checked_add(vote.reward_share)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    reward_ledger.total_credited = reward_ledger
+        .total_credited
+        .checked_add(vote.reward_share)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Emit an event for the validator reward
+    emit!(ValidatorRewardCredited {
+        report_id,
+        voter: voter.key(),
+        amount: vote.reward_share,
+    });
+
+    Ok(())
+}
+
+/// Records `amount` in the vault accounting. Credited rewards stay in the
+/// vault until claimed, so the vault must cover everything outstanding plus
+/// this reward.
+fn reserve_reward(state: &mut ProgramState, vault_balance: u64, amount: u64) -> Result<()> {
+    let outstanding = state
+        .total_rewards_distributed
+        .saturating_sub(state.total_rewards_claimed)
+        .saturating_sub(state.total_rewards_clawed_back);
+    if vault_balance < outstanding.saturating_add(amount) {
+        return Err(ErrorCode::InsufficientFunds.into());
+    }
+
+    state.total_rewards_distributed = state
+        .total_rewards_distributed
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Adds a vesting grant to `reward_ledger` and reserves it in the vault.
+fn credit_reward(
+    reward_ledger: &mut RewardLedger,
+    state: &mut ProgramState,
+    vault_balance: u64,
+    report_id: u64,
+    amount: u64,
+    params: &RewardParams,
+    now: i64,
+) -> Result<RewardGrant> {
+    reward_ledger.prune_settled();
+    if reward_ledger.grants.len() >= RewardLedger::MAX_GRANTS {
+        return Err(ErrorCode::RewardLedgerFull.into());
+    }
+
+    let grant = RewardGrant {
+        report_id,
+        amount,
+        claimed: 0,
+        start: now,
+        cliff: now.saturating_add(params.vesting_cliff_seconds),
+        end: now.saturating_add(params.vesting_duration_seconds),
+    };
+    reward_ledger.grants.push(grant.clone());
+    reward_ledger.total_credited = reward_ledger
+        .total_credited
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    reserve_reward(state, vault_balance, amount)?;
+
+    Ok(grant)
+}
+
+pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let reward_ledger = &mut ctx.accounts.reward_ledger;
+    let state = &mut ctx.accounts.state;
+    let now = Clock::get()?.unix_timestamp;
 
-    // Transfer tokens from reward vault to submitter
+    let amount = reward_ledger.claimable(now);
+    if amount == 0 {
+        return Err(ErrorCode::NothingToClaim.into());
+    }
+
+    // Transfer vested tokens from reward vault to the ledger owner
     let transfer_instruction = Transfer {
-        from: reward_vault.to_account_info(),
-        to: submitter_token_account.to_account_info(),
-        authority: program_authority.to_account_info(),
+        from: ctx.accounts.reward_vault.to_account_info(),
+        to: ctx.accounts.owner_token_account.to_account_info(),
+        authority: ctx.accounts.program_authority.to_account_info(),
     };
 
     token::transfer(
         CpiContext::new_with_signer(
-            token_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
             transfer_instruction,
-            &[&[b"authority".as_ref(), &[ctx.bumps.program_authority]]]
+            &[&[b"authority".as_ref(), &[*ctx.bumps.get("program_authority").unwrap()]]]
         ),
-        reward_amount
+        amount
     )?;
 
-    // Mark the report as rewarded
-    report.rewarded = true;
+    reward_ledger.liquid = 0;
+    for grant in reward_ledger.grants.iter_mut() {
+        grant.claimed = grant.vested(now);
+    }
+    reward_ledger.prune_settled();
+    reward_ledger.total_claimed = reward_ledger
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    state.total_rewards_claimed = state
+        .total_rewards_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-    // Emit an event for the reward distribution
-    emit!(RewardDistributed {
+    // Emit an event for the claim
+    emit!(RewardsClaimed {
+        owner: reward_ledger.owner,
+        amount,
+    });
+
+    msg!("Claimed {} tokens for: {}", amount, reward_ledger.owner);
+
+    Ok(())
+}
+
+/// Besides the authority and council, users at the moderation tier may flag
+/// reports by passing their reputation account as the first remaining account.
+pub fn flag_fraudulent_report<'info>(ctx: Context<'_, '_, '_, 'info, FlagFraudulentReport<'info>>, report_id: u64) -> Result<()> {
+    let report = &mut ctx.accounts.report;
+    let state = &mut ctx.accounts.state;
+    let submitter_reputation = &mut ctx.accounts.submitter_reputation;
+    let config = &ctx.accounts.config.params;
+    let decided_by = &ctx.accounts.decided_by;
+
+    // Fraud findings are made by the program authority, a council member or
+    // a moderation-tier user
+    let mut successor_accounts = ctx.remaining_accounts;
+    if decided_by.key() != state.authority && !config.is_council_member(&decided_by.key()) {
+        check_moderator_tier(ctx.remaining_accounts, &decided_by.key(), config)?;
+        successor_accounts = &ctx.remaining_accounts[1..];
+    }
+
+    // Votes on an approval settle once its fraud window closes, and settled
+    // incentives would not follow the winning side over
+    let now = Clock::get()?.unix_timestamp;
+    if report.winning_vote == Some(VoteType::Upvote) && now > report.fraud_window_end(&config.rewards) {
+        return Err(ErrorCode::FraudWindowClosed.into());
+    }
+
+    let was_open = report.is_open();
+    transition_report_status(report, ReportStatus::Rejected)?;
+    if was_open {
+        submitter_reputation.report_closed();
+    }
+
+    // Downvoters are the winning side now. No vote has been settled yet, see
+    // `SettleVotes`
+    if report.winning_vote == Some(VoteType::Upvote) {
+        report.winning_vote = Some(VoteType::Downvote);
+        report.winning_weight = side_weights(report).1;
+    }
+
+    // A submitter who moved to a new wallet took their reputation, history
+    // and reward grants along, so the clawback and penalty follow them
+    let mut successor = load_successor(successor_accounts, submitter_reputation.migrated_to)?;
+    let (reputation, history, reward_ledger) = match successor.as_mut() {
+        Some((reputation, history, reward_ledger)) => (&mut **reputation, &mut **history, &*reward_ledger),
+        None => (
+            &mut **submitter_reputation,
+            &mut **ctx.accounts.submitter_history,
+            &ctx.accounts.reward_ledger,
+        ),
+    };
+
+    // Claw back whatever part of the reward has not vested yet
+    let clawed_back = if report.reward_distributed {
+        claw_back_report_grant(reward_ledger, report_id, now)?
+    } else {
+        0
+    };
+    state.total_rewards_clawed_back = state
+        .total_rewards_clawed_back
+        .checked_add(clawed_back)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Forfeit a bond that is still held
+    let forfeited_bond = std::mem::take(&mut report.bond);
+    **report.to_account_info().try_borrow_mut_lamports()? -= forfeited_bond;
+    **state.to_account_info().try_borrow_mut_lamports()? += forfeited_bond;
+
+    // Penalize the submitter
+    reputation.apply_decay(config, now);
+    let category_change = reputation.change_category_score(&report.category, -config.fraud_reputation_penalty, config, now);
+    let old_score = reputation.reputation_score;
+    let penalized_score = old_score.saturating_sub(config.fraud_reputation_penalty);
+    reputation.set_score(penalized_score.clamp(config.min_reputation, config.max_reputation), now);
+    reputation.refresh_tier(&config.tiers);
+    if let Some(entry) = history.record(
+        reputation.reputation_score - old_score,
+        ReputationChangeReason::FraudPenalty,
+        Some(report_id),
+        decided_by.key(),
+        now,
+    ) {
+        entry.set_category_change(report.category, category_change);
+    }
+    if let Some((reputation, history, _)) = successor {
+        reputation.exit(&crate::ID)?;
+        history.exit(&crate::ID)?;
+    }
+
+    // Emit an event for the clawback
+    emit!(RewardClawedBack {
         report_id,
-        submitter: *submitter.key,
-        amount: reward_amount,
+        submitter: report.submitter,
+        amount: clawed_back,
+        reputation_penalty: config.fraud_reputation_penalty,
+        decided_by: decided_by.key(),
     });
 
-    msg!("Distributed {} tokens to submitter: {}", reward_amount, submitter.key);
+    msg!("Report {} overturned as fraudulent, clawed back {} tokens", report_id, clawed_back);
 
     Ok(())
 }
 
-fn calculate_reward_amount(report: &Report) -> Result<u64> {
-    // TODO: Implement a more sophisticated reward calculation based on report quality, user reputation, etc.
-    // For now, we'll use a fixed amount
-    Ok(FIXED_REWARD_AMOUNT)
+// Reputation, reputation history and reward ledger of one wallet
+type IdentityAccounts<'info> = (Account<'info, UserReputation>, Account<'info, ReputationHistory>, AccountInfo<'info>);
+
+// Follows `migrated_to` to the wallet the identity lives at now. Each step
+// takes that wallet's reputation, reputation history and reward ledger, in
+// that order, from the remaining accounts. Returns `None` when the identity
+// never moved.
+fn load_successor<'info>(
+    accounts: &[AccountInfo<'info>],
+    mut migrated_to: Option<Pubkey>,
+) -> Result<Option<IdentityAccounts<'info>>> {
+    let mut triples = accounts.chunks_exact(3);
+    let mut successor = None;
+    while let Some(user) = migrated_to {
+        let triple = triples.next().ok_or(ErrorCode::InvalidRemainingAccounts)?;
+        let reputation = Account::<UserReputation>::try_from(&triple[0])?;
+        let history = Account::<ReputationHistory>::try_from(&triple[1])?;
+        let reward_ledger = triple[2].clone();
+
+        // Reputation and history accounts are only ever created at their
+        // PDAs, so matching fields identify them. The ledger may not exist
+        // yet, so its address is checked instead.
+        let (ledger_address, _) = Pubkey::find_program_address(&[b"reward_ledger", user.as_ref()], &crate::ID);
+        if reputation.user != user || history.user != user || reward_ledger.key() != ledger_address {
+            return Err(ErrorCode::InvalidRemainingAccounts.into());
+        }
+
+        migrated_to = reputation.migrated_to;
+        successor = Some((reputation, history, reward_ledger));
+    }
+    Ok(successor)
+}
+
+fn claw_back_report_grant(ledger_info: &AccountInfo, report_id: u64, now: i64) -> Result<u64> {
+    let mut reward_ledger = Account::<RewardLedger>::try_from(ledger_info)?;
+
+    let clawed_back = match reward_ledger.grants.iter_mut().find(|grant| grant.report_id == report_id) {
+        Some(grant) => grant.claw_back(now),
+        None => 0,
+    };
+    reward_ledger.total_credited = reward_ledger.total_credited.saturating_sub(clawed_back);
+    reward_ledger.prune_settled();
+
+    reward_ledger.exit(&crate::ID)?;
+    Ok(clawed_back)
+}
+
+pub fn quote_reward(ctx: Context<QuoteReward>, _report_id: u64) -> Result<u64> {
+    let config = &ctx.accounts.config.params;
+    let submitter_score = ctx
+        .accounts
+        .submitter_reputation
+        .effective_score(config, Clock::get()?.unix_timestamp);
+    calculate_reward_amount(&ctx.accounts.report, submitter_score, config)
+}
+
+// Reward = category base, scaled up by the vote margin above the approval
+// threshold and by the submitter's reputation tier, capped per report.
+fn calculate_reward_amount(report: &Report, submitter_score: i64, config: &ConfigParams) -> Result<u64> {
+    let params = &config.rewards;
+    let base = params.category_base_amounts[report.category.index()];
+
+    let margin = report.votes.saturating_sub(config.approval_threshold).max(0) as u64;
+    let vote_bonus_bps = margin
+        .saturating_mul(params.vote_margin_bonus_bps as u64)
+        .min(params.max_vote_margin_bonus_bps as u64);
+
+    let reputation_bonus_bps = params
+        .reputation_bonus_tiers
+        .iter()
+        .rev()
+        .find(|tier| submitter_score >= tier.min_reputation)
+        .map_or(0, |tier| tier.bonus_bps as u64);
+
+    let amount = (base as u128)
+        .checked_mul(BPS_DENOMINATOR + vote_bonus_bps as u128)
+        .and_then(|v| v.checked_mul(BPS_DENOMINATOR + reputation_bonus_bps as u128))
+        .map(|v| v / (BPS_DENOMINATOR * BPS_DENOMINATOR))
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    Ok(amount.min(params.max_reward_per_report as u128) as u64)
 }
 
 // Event emitted when a reward is distributed
@@ -85,15 +762,42 @@ pub struct RewardDistributed {
     pub report_id: u64,
     pub submitter: Pubkey,
     pub amount: u64,
+    pub vesting_cliff: i64,
+    pub vesting_end: i64,
+}
+
+// Event emitted when a fraudulent report's unvested reward is clawed back
+#[event]
+pub struct RewardClawedBack {
+    pub report_id: u64,
+    pub submitter: Pubkey,
+    pub amount: u64,
+    pub reputation_penalty: i64,
+    pub decided_by: Pubkey,
+}
+
+// Event emitted when a voter's share of the validator pool is credited
+#[event]
+pub struct ValidatorRewardCredited {
+    pub report_id: u64,
+    pub voter: Pubkey,
+    pub amount: u64,
+}
+
+// Event emitted when vested rewards are claimed
+#[event]
+pub struct RewardsClaimed {
+    pub owner: Pubkey,
+    pub amount: u64,
 }
 
 // Constants
-const FIXED_REWARD_AMOUNT: u64 = 100; // Adjust this value as needed
+const BPS_DENOMINATOR: u128 = 10_000;
 
 // Add this to your lib.rs or main program file
 pub fn initialize_reward_vault(ctx: Context<InitializeRewardVault>, amount: u64) -> Result<()> {
     let cpi_accounts = Transfer {
-        from: ctx.accounts.funder.to_account_info(),
+        from: ctx.accounts.funder_token_account.to_account_info(),
         to: ctx.accounts.reward_vault.to_account_info(),
         authority: ctx.accounts.funder.to_account_info(),
     };
@@ -105,21 +809,159 @@ pub fn initialize_reward_vault(ctx: Context<InitializeRewardVault>, amount: u64)
     Ok(())
 }
 
+/// The vault fixes the reward mint for good, so only the program authority
+/// may create it.
 #[derive(Accounts)]
 pub struct InitializeRewardVault<'info> {
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub state: Account<'info, ProgramState>,
+    pub authority: Signer<'info>,
     #[account(mut)]
     pub funder: Signer<'info>,
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ ErrorCode::InvalidTokenAccount,
+        constraint = funder_token_account.mint == mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
     #[account(
         init,
         payer = funder,
+        seeds = [b"reward_vault"],
+        bump,
         token::mint = mint,
         token::authority = program_authority,
     )]
     pub reward_vault: Account<'info, TokenAccount>,
-    pub mint: Account<'info, token::Mint>,
+    pub mint: Account<'info, Mint>,
+    /// CHECK: PDA that owns the vault; only its address is used
     #[account(seeds = [b"authority".as_ref()], bump)]
     pub program_authority: AccountInfo<'info>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report_with(category: ReportCategory, votes: i64) -> Report {
+        Report {
+            report_id: 1,
+            bump: 0,
+            submitter: Pubkey::default(),
+            description: String::new(),
+            location: String::new(),
+            media_hash: String::new(),
+            timestamp: 0,
+            votes,
+            raw_votes: votes,
+            total_vote_weight: votes.unsigned_abs(),
+            voting_deadline: 0,
+            reveal_deadline: 0,
+            commit_reveal: false,
+            quadratic: false,
+            winning_vote: None,
+            winning_weight: votes.unsigned_abs(),
+            category,
+            status: ReportStatus::Approved,
+            left_submitted_at: None,
+            escalation: None,
+            reward_distributed: false,
+            bond: 0,
+        }
+    }
+
+    fn grant_with(cliff: i64, end: i64) -> RewardGrant {
+        RewardGrant {
+            report_id: 1,
+            amount: 1_000,
+            claimed: 0,
+            start: 0,
+            cliff,
+            end,
+        }
+    }
+
+    #[test]
+    fn grant_vests_linearly_from_start_after_the_cliff() {
+        let grant = grant_with(100, 1_000);
+        assert_eq!(grant.vested(99), 0);
+        assert_eq!(grant.vested(100), 100);
+        assert_eq!(grant.vested(500), 500);
+        assert_eq!(grant.vested(1_000), 1_000);
+        assert_eq!(grant.vested(5_000), 1_000);
+    }
+
+    #[test]
+    fn grant_with_cliff_at_end_vests_all_at_once() {
+        let grant = grant_with(500, 500);
+        assert_eq!(grant.vested(499), 0);
+        assert_eq!(grant.vested(500), 1_000);
+
+        // Zero vesting duration
+        let grant = grant_with(0, 0);
+        assert_eq!(grant.vested(-1), 0);
+        assert_eq!(grant.vested(0), 1_000);
+    }
+
+    #[test]
+    fn claw_back_after_partial_claim_keeps_vested_remainder_claimable() {
+        let mut grant = grant_with(100, 1_000);
+        grant.claimed = grant.vested(400);
+
+        assert_eq!(grant.claw_back(600), 400);
+        assert_eq!(grant.amount, 600);
+        assert_eq!(grant.claimable(600), 200);
+        assert_eq!(grant.claimable(5_000), 200);
+    }
+
+    #[test]
+    fn claw_back_before_cliff_removes_everything() {
+        let mut grant = grant_with(100, 1_000);
+
+        assert_eq!(grant.claw_back(50), 1_000);
+        assert_eq!(grant.claimable(50), 0);
+        assert_eq!(grant.claimable(5_000), 0);
+    }
+
+    #[test]
+    fn reward_is_category_base_without_bonuses() {
+        let config = ConfigParams::default();
+        let report = report_with(ReportCategory::RoadIssue, config.approval_threshold);
+        assert_eq!(calculate_reward_amount(&report, 0, &config).unwrap(), 100);
+
+        let report = report_with(ReportCategory::Other, config.approval_threshold);
+        assert_eq!(calculate_reward_amount(&report, -500, &config).unwrap(), 50);
+    }
+
+    #[test]
+    fn reward_compounds_vote_margin_and_reputation_bonuses() {
+        let config = ConfigParams::default();
+
+        // 2 votes over the threshold add 10%, a score of 200 adds 15%
+        let report = report_with(ReportCategory::RoadIssue, config.approval_threshold + 2);
+        assert_eq!(calculate_reward_amount(&report, 200, &config).unwrap(), 126);
+
+        // Scores between tiers get the highest tier reached
+        assert_eq!(calculate_reward_amount(&report, 499, &config).unwrap(), 126);
+        assert_eq!(calculate_reward_amount(&report, 49, &config).unwrap(), 110);
+    }
+
+    #[test]
+    fn reward_caps_vote_margin_bonus_and_total() {
+        let mut config = ConfigParams::default();
+
+        // The margin bonus stops at 50%, the top reputation tier adds 30%
+        let report = report_with(ReportCategory::RoadIssue, 1_000);
+        assert_eq!(calculate_reward_amount(&report, 1_000, &config).unwrap(), 195);
+
+        config.rewards.max_reward_per_report = 150;
+        assert_eq!(calculate_reward_amount(&report, 1_000, &config).unwrap(), 150);
+    }
+}
@@ -1,15 +1,28 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use crate::config::Config;
+use crate::escalation::EscalationDetails;
 use crate::reputation::UserReputation;
-use crate::ErrorCode;
+use crate::rewards::RewardParams;
+use crate::voting::VoteType;
+use crate::{ErrorCode, ProgramState};
 
 #[derive(Accounts)]
 pub struct SubmitReport<'info> {
     #[account(mut)]
     pub submitter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
     #[account(
         init,
         payer = submitter,
-        space = 8 + 32 + 256 + 64 + 256 + 8 + 8 + 1 + 32 + 8
+        space = 8 + Report::LEN,
+        seeds = [b"report", state.report_count.to_le_bytes().as_ref()],
+        bump
     )]
     pub report: Account<'info, Report>,
     #[account(
@@ -18,24 +31,122 @@ pub struct SubmitReport<'info> {
         bump
     )]
     pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
     pub system_program: Program<'info, System>,
 }
 
+/// Pays out a held submission bond once the report is decided; permissionless
+/// so anyone can crank it.
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct ReleaseSubmissionBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
+        constraint = report.bond > 0 @ ErrorCode::NoSubmissionBond
+    )]
+    pub report: Account<'info, Report>,
+    /// CHECK: receives the bond back when the report was upheld
+    #[account(mut, address = report.submitter @ ErrorCode::UserMismatch)]
+    pub submitter: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump = state.bump
+    )]
+    pub state: Account<'info, ProgramState>,
+}
+
 #[account]
 pub struct Report {
+    pub report_id: u64,
+    pub bump: u8,
     pub submitter: Pubkey,
     pub description: String,
     pub location: String,
     pub media_hash: String,
     pub timestamp: i64,
+    /// Net reputation-weighted votes; thresholds apply to this tally.
     pub votes: i64,
+    /// Net unweighted votes (upvotes minus downvotes).
+    pub raw_votes: i64,
+    /// Sum of the weights of every vote cast, used for quorum.
+    pub total_vote_weight: u64,
+    /// End of voting, or of the commit phase in commit-reveal mode.
+    pub voting_deadline: i64,
+    /// End of the reveal phase; equals `voting_deadline` for direct votes.
+    pub reveal_deadline: i64,
+    pub commit_reveal: bool,
+    /// Votes are cast with `cast_quadratic_vote` and paid for in voice credits.
+    pub quadratic: bool,
+    /// Side that won at finalization and its total weight, used to settle
+    /// voter incentives. `None` until finalized with quorum.
+    pub winning_vote: Option<VoteType>,
+    pub winning_weight: u64,
     pub category: ReportCategory,
     pub status: ReportStatus,
-    pub escalation: Option<Pubkey>,
+    /// When the report first left `Submitted`; votes can only be revealed
+    /// before then.
+    pub left_submitted_at: Option<i64>,
+    pub escalation: Option<EscalationDetails>,
     pub reward_distributed: bool,
+    /// Lamports held in this account on top of rent until the report is
+    /// decided; zero when waived or already paid out.
+    pub bond: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+impl Report {
+    pub const MAX_DESCRIPTION_LEN: usize = 256;
+    pub const MAX_LOCATION_LEN: usize = 64;
+    pub const MAX_MEDIA_HASH_LEN: usize = 256;
+
+    pub const LEN: usize = 8 // report_id
+        + 1 // bump
+        + 32 // submitter
+        + 4 + Self::MAX_DESCRIPTION_LEN
+        + 4 + Self::MAX_LOCATION_LEN
+        + 4 + Self::MAX_MEDIA_HASH_LEN
+        + 8 // timestamp
+        + 8 // votes
+        + 8 // raw_votes
+        + 8 // total_vote_weight
+        + 8 // voting_deadline
+        + 8 // reveal_deadline
+        + 1 // commit_reveal
+        + 1 // quadratic
+        + 1 + 1 // winning_vote
+        + 8 // winning_weight
+        + 1 // category
+        + 1 // status
+        + 1 + 8 // left_submitted_at
+        + 1 + EscalationDetails::LEN
+        + 1 // reward_distributed
+        + 8; // bond
+
+    /// End of the time in which an approval by vote can be overturned as
+    /// fraudulent: one vesting period after the reveal deadline, about as
+    /// long as a reward distributed at finalization can be clawed back.
+    pub fn fraud_window_end(&self, params: &RewardParams) -> i64 {
+        self.reveal_deadline.saturating_add(params.vesting_duration_seconds)
+    }
+
+    /// Whether the report can still change its submitter's reputation or
+    /// pay them a reward, counted in `UserReputation::open_reports`.
+    pub fn is_open(&self) -> bool {
+        match self.status {
+            ReportStatus::Submitted | ReportStatus::UnderReview => true,
+            ReportStatus::Approved => !self.reward_distributed,
+            ReportStatus::Resolved | ReportStatus::Rejected => false,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum ReportCategory {
     RoadIssue,
     StreetLight,
@@ -44,6 +155,21 @@ pub enum ReportCategory {
     Other,
 }
 
+impl ReportCategory {
+    pub const COUNT: usize = 5;
+
+    /// Position of the category in per-category config tables.
+    pub fn index(&self) -> usize {
+        match self {
+            ReportCategory::RoadIssue => 0,
+            ReportCategory::StreetLight => 1,
+            ReportCategory::PublicFacility => 2,
+            ReportCategory::EnvironmentalConcern => 3,
+            ReportCategory::Other => 4,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum ReportStatus {
     Submitted,
@@ -53,6 +179,31 @@ pub enum ReportStatus {
     Rejected,
 }
 
+impl ReportStatus {
+    /// Returns whether a report may move from this status to `next`.
+    /// `Rejected` is terminal; `Resolved` can only be overturned.
+    pub fn can_transition_to(&self, next: &ReportStatus) -> bool {
+        use ReportStatus::*;
+        matches!(
+            (self, next),
+            (Submitted, UnderReview)
+                | (Submitted, Approved)
+                | (Submitted, Rejected)
+                | (UnderReview, Approved)
+                | (UnderReview, Rejected)
+                | (UnderReview, Resolved)
+                | (Approved, Resolved)
+        ) || self.is_overturn(next)
+    }
+
+    /// Rejecting a report that was already approved. Only a fraud finding may
+    /// do this, so that any reward is clawed back.
+    pub fn is_overturn(&self, next: &ReportStatus) -> bool {
+        use ReportStatus::*;
+        matches!((self, next), (Approved, Rejected) | (Resolved, Rejected))
+    }
+}
+
 pub fn submit_report(
     ctx: Context<SubmitReport>,
     description: String,
@@ -60,42 +211,127 @@ pub fn submit_report(
     media_hash: String,
     category: ReportCategory,
 ) -> Result<()> {
+    let state = &mut ctx.accounts.state;
     let report = &mut ctx.accounts.report;
     let submitter = &ctx.accounts.submitter;
     let submitter_reputation = &mut ctx.accounts.submitter_reputation;
+    let config = &ctx.accounts.config.params;
 
     // Validate input
-    if description.len() > 256 || location.len() > 64 || media_hash.len() > 256 {
+    if description.len() > Report::MAX_DESCRIPTION_LEN
+        || location.len() > Report::MAX_LOCATION_LEN
+        || media_hash.len() > Report::MAX_MEDIA_HASH_LEN
+    {
         return Err(ErrorCode::InputTooLong.into());
     }
 
+    let now = Clock::get()?.unix_timestamp;
+    submitter_reputation.apply_decay(config, now);
+
     // Check if the submitter has sufficient reputation to submit a report
-    if submitter_reputation.reputation_score < MINIMUM_REPUTATION_FOR_SUBMISSION {
+    if submitter_reputation.reputation_score < config.minimum_reputation_for_submission {
         return Err(ErrorCode::InsufficientReputation.into());
     }
+    config.eligibility.check_submitter(submitter_reputation, now)?;
 
+    // Hold a bond in the report account unless the submitter's tier waives it
+    let tier = config.tiers.tier_for(submitter_reputation.reputation_score);
+    let bond = if config.tiers.permissions(tier).submission_bond_waived {
+        0
+    } else {
+        config.submission_bond_lamports
+    };
+    if bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: submitter.to_account_info(),
+                    to: report.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+    }
+
+    // Assign the next sequential report number
+    let report_id = state.report_count;
+    state.report_count = state.report_count.checked_add(1).ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    report.report_id = report_id;
+    report.bump = *ctx.bumps.get("report").unwrap();
     report.submitter = *submitter.key;
     report.description = description;
     report.location = location;
     report.media_hash = media_hash;
-    report.timestamp = Clock::get()?.unix_timestamp;
+    report.timestamp = now;
     report.votes = 0;
+    report.raw_votes = 0;
+    report.total_vote_weight = 0;
+    report.winning_vote = None;
+    report.winning_weight = 0;
+    report.voting_deadline = report.timestamp.saturating_add(config.voting.voting_period_seconds);
+    report.commit_reveal = config.voting.commit_reveal_categories[category.index()];
+    report.reveal_deadline = if report.commit_reveal {
+        report.voting_deadline.saturating_add(config.voting.reveal_period_seconds)
+    } else {
+        report.voting_deadline
+    };
+    // Quadratic voting has no commit phase, so commit-reveal takes precedence
+    report.quadratic = config.voting.quadratic_voting && !report.commit_reveal;
     report.category = category;
     report.status = ReportStatus::Submitted;
+    report.left_submitted_at = None;
     report.escalation = None;
     report.reward_distributed = false;
+    report.bond = bond;
 
     // Update submitter's reputation
     submitter_reputation.reports_submitted += 1;
+    submitter_reputation.open_reports += 1;
 
     // Emit an event for the new report submission
     emit!(ReportSubmitted {
+        report_id,
         report_pubkey: report.key(),
         submitter: *submitter.key,
-        category: report.category.clone(),
+        category: report.category,
+        bond,
     });
 
-    msg!("Report submitted by: {}", submitter.key);
+    msg!("Report {} submitted by: {}", report_id, submitter.key);
+
+    Ok(())
+}
+
+/// Returns the bond to the submitter once the report is approved or
+/// resolved, and forfeits it to the program once rejected.
+pub fn release_submission_bond(ctx: Context<ReleaseSubmissionBond>, report_id: u64) -> Result<()> {
+    let report = &mut ctx.accounts.report;
+
+    let refunded = match report.status {
+        ReportStatus::Approved | ReportStatus::Resolved => true,
+        ReportStatus::Rejected => false,
+        _ => return Err(ErrorCode::ReportNotFinalized.into()),
+    };
+    let recipient = if refunded {
+        ctx.accounts.submitter.to_account_info()
+    } else {
+        ctx.accounts.state.to_account_info()
+    };
+
+    let amount = std::mem::take(&mut report.bond);
+    let report_info = report.to_account_info();
+    **report_info.try_borrow_mut_lamports()? -= amount;
+    **recipient.try_borrow_mut_lamports()? += amount;
+
+    // Emit an event for the released bond
+    emit!(SubmissionBondReleased {
+        report_id,
+        submitter: report.submitter,
+        amount,
+        refunded,
+    });
 
     Ok(())
 }
@@ -103,31 +339,55 @@ pub fn submit_report(
 // Event emitted when a new report is submitted
 #[event]
 pub struct ReportSubmitted {
+    pub report_id: u64,
     pub report_pubkey: Pubkey,
     pub submitter: Pubkey,
     pub category: ReportCategory,
+    pub bond: u64,
 }
 
-// Constants
-const MINIMUM_REPUTATION_FOR_SUBMISSION: i64 = -500; // Adjust this value as needed
+// Event emitted when a submission bond is refunded or forfeited
+#[event]
+pub struct SubmissionBondReleased {
+    pub report_id: u64,
+    pub submitter: Pubkey,
+    pub amount: u64,
+    pub refunded: bool,
+}
 
-// Add this to your lib.rs or main program file
-pub fn update_report_status(ctx: Context<UpdateReportStatus>, new_status: ReportStatus) -> Result<()> {
+pub fn update_report_status(ctx: Context<UpdateReportStatus>, _report_id: u64, new_status: ReportStatus) -> Result<()> {
     let report = &mut ctx.accounts.report;
-    let authority = &ctx.accounts.authority;
 
-    // Ensure only the program authority can update report status
-    if authority.key() != ctx.accounts.program_authority.key() {
-        return Err(ErrorCode::Unauthorized.into());
+    if report.status.is_overturn(&new_status) {
+        return Err(ErrorCode::InvalidStatusTransition.into());
+    }
+
+    let was_open = report.is_open();
+    transition_report_status(report, new_status)?;
+    if was_open && !report.is_open() {
+        ctx.accounts.submitter_reputation.report_closed();
+    }
+
+    Ok(())
+}
+
+/// Moves `report` to `new_status` if the transition table allows it and emits
+/// `ReportStatusUpdated`. Every status write in the program goes through here.
+pub fn transition_report_status(report: &mut Account<Report>, new_status: ReportStatus) -> Result<()> {
+    if !report.status.can_transition_to(&new_status) {
+        return Err(ErrorCode::InvalidStatusTransition.into());
     }
 
-    // Update report status
-    report.status = new_status;
+    let old_status = std::mem::replace(&mut report.status, new_status.clone());
+    if old_status == ReportStatus::Submitted {
+        report.left_submitted_at = Some(Clock::get()?.unix_timestamp);
+    }
 
     // Emit an event for the status update
     emit!(ReportStatusUpdated {
+        report_id: report.report_id,
         report_pubkey: report.key(),
-        old_status: report.status.clone(),
+        old_status,
         new_status,
     });
 
@@ -135,18 +395,34 @@ pub fn update_report_status(ctx: Context<UpdateReportStatus>, new_status: Report
 }
 
 #[derive(Accounts)]
+#[instruction(report_id: u64)]
 pub struct UpdateReportStatus<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump
+    )]
     pub report: Account<'info, Report>,
+    #[account(
+        mut,
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"state"],
+        bump = state.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub state: Account<'info, ProgramState>,
     pub authority: Signer<'info>,
-    #[account(seeds = [b"authority".as_ref()], bump)]
-    pub program_authority: AccountInfo<'info>,
 }
 
 // Event emitted when a report's status is updated
 #[event]
 pub struct ReportStatusUpdated {
+    pub report_id: u64,
     pub report_pubkey: Pubkey,
     pub old_status: ReportStatus,
     pub new_status: ReportStatus,
-}
\ No newline at end of file
+}
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use crate::config::{Config, ConfigParams};
+use crate::reputation::UserReputation;
+use crate::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct GetReputationTier<'info> {
+    #[account(
+        seeds = [b"reputation", user.as_ref()],
+        bump,
+        constraint = user_reputation.user == user @ ErrorCode::UserMismatch
+    )]
+    pub user_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Standing derived from the global reputation score. Variants are ordered
+/// from lowest to highest, so tiers compare with `>=`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReputationTier {
+    #[default]
+    Newcomer,
+    Contributor,
+    Validator,
+    Steward,
+}
+
+impl ReputationTier {
+    pub const COUNT: usize = 4;
+
+    /// Position of the tier in per-tier config tables.
+    pub fn index(&self) -> usize {
+        match self {
+            ReputationTier::Newcomer => 0,
+            ReputationTier::Contributor => 1,
+            ReputationTier::Validator => 2,
+            ReputationTier::Steward => 3,
+        }
+    }
+}
+
+/// What a tier unlocks under the current config.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct TierPermissions {
+    /// Cap applied on top of the reputation-based vote weight.
+    pub max_vote_weight: u64,
+    pub can_escalate: bool,
+    /// May flag fraudulent reports and review appeals.
+    pub can_moderate: bool,
+    pub submission_bond_waived: bool,
+}
+
+/// Returned by `get_reputation_tier`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct TierStatus {
+    pub tier: ReputationTier,
+    /// Decayed score the tier was computed from.
+    pub reputation_score: i64,
+    pub permissions: TierPermissions,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TierParams {
+    /// Minimum global score for each tier above Newcomer.
+    pub contributor_threshold: i64,
+    pub validator_threshold: i64,
+    pub steward_threshold: i64,
+    /// Vote weight cap for each tier, indexed by `ReputationTier::index`.
+    pub max_vote_weight: [u64; ReputationTier::COUNT],
+    /// Lowest tier allowed to escalate reports.
+    pub escalation_tier: ReputationTier,
+    /// Lowest tier allowed to moderate besides the authority and council.
+    pub moderation_tier: ReputationTier,
+    /// Lowest tier that submits reports without a bond.
+    pub bond_waiver_tier: ReputationTier,
+}
+
+impl TierParams {
+    pub const LEN: usize = 8 // contributor_threshold
+        + 8 // validator_threshold
+        + 8 // steward_threshold
+        + 8 * ReputationTier::COUNT // max_vote_weight
+        + 1 // escalation_tier
+        + 1 // moderation_tier
+        + 1; // bond_waiver_tier
+
+    /// New accounts start at zero, so the Contributor threshold must be
+    /// positive for everyone to start as a Newcomer.
+    pub fn validate(&self) -> Result<()> {
+        if self.contributor_threshold <= 0
+            || self.validator_threshold <= self.contributor_threshold
+            || self.steward_threshold <= self.validator_threshold
+        {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        let caps_increase = self.max_vote_weight.windows(2).all(|pair| pair[0] <= pair[1]);
+        if self.max_vote_weight[0] == 0 || !caps_increase {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        Ok(())
+    }
+
+    pub fn tier_for(&self, reputation_score: i64) -> ReputationTier {
+        if reputation_score >= self.steward_threshold {
+            ReputationTier::Steward
+        } else if reputation_score >= self.validator_threshold {
+            ReputationTier::Validator
+        } else if reputation_score >= self.contributor_threshold {
+            ReputationTier::Contributor
+        } else {
+            ReputationTier::Newcomer
+        }
+    }
+
+    pub fn permissions(&self, tier: ReputationTier) -> TierPermissions {
+        TierPermissions {
+            max_vote_weight: self.max_vote_weight[tier.index()],
+            can_escalate: tier >= self.escalation_tier,
+            can_moderate: tier >= self.moderation_tier,
+            submission_bond_waived: tier >= self.bond_waiver_tier,
+        }
+    }
+}
+
+impl Default for TierParams {
+    fn default() -> Self {
+        Self {
+            contributor_threshold: 100,
+            validator_threshold: 400,
+            steward_threshold: 800,
+            max_vote_weight: [3, 6, 10, 10],
+            escalation_tier: ReputationTier::Contributor,
+            moderation_tier: ReputationTier::Steward,
+            bond_waiver_tier: ReputationTier::Contributor,
+        }
+    }
+}
+
+/// Checks that `moderator` reached the moderation tier, judged from their
+/// reputation account passed as the first remaining account. Used by
+/// instructions that otherwise only admit the authority or appointed members.
+pub fn check_moderator_tier<'info>(remaining_accounts: &[AccountInfo<'info>], moderator: &Pubkey, config: &ConfigParams) -> Result<()> {
+    let reputation_info = remaining_accounts.first().ok_or(ErrorCode::Unauthorized)?;
+    let reputation = Account::<UserReputation>::try_from(reputation_info)?;
+
+    // Reputation accounts are only created at the PDA of their user
+    if reputation.user != *moderator {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+    reputation.is_active()?;
+
+    let score = reputation.effective_score(config, Clock::get()?.unix_timestamp);
+    if !config.tiers.permissions(config.tiers.tier_for(score)).can_moderate {
+        return Err(ErrorCode::InsufficientTier.into());
+    }
+    Ok(())
+}
+
+/// Computed from the decayed score, so it can differ from the tier stored on
+/// the account until its next update.
+pub fn get_reputation_tier(ctx: Context<GetReputationTier>, _user: Pubkey) -> Result<TierStatus> {
+    let config = &ctx.accounts.config.params;
+    let reputation_score = ctx
+        .accounts
+        .user_reputation
+        .effective_score(config, Clock::get()?.unix_timestamp);
+    let tier = config.tiers.tier_for(reputation_score);
+
+    Ok(TierStatus {
+        tier,
+        reputation_score,
+        permissions: config.tiers.permissions(tier),
+    })
+}
+
+// Event emitted when a user's score moves them to another tier
+#[event]
+pub struct TierChanged {
+    pub user: Pubkey,
+    pub old_tier: ReputationTier,
+    pub new_tier: ReputationTier,
+    pub reputation_score: i64,
+}
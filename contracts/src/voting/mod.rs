@@ -1,8 +1,15 @@
 use anchor_lang::prelude::*;
-use crate::submission::{Report, ReportStatus};
-use crate::reputation::UserReputation;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use crate::config::{Config, ConfigParams};
+use crate::delegation::Delegation;
+use crate::submission::{transition_report_status, Report, ReportCategory, ReportStatus};
+use crate::reputation::{ReputationChangeReason, ReputationHistory, UserReputation};
 use crate::ErrorCode;
 
+/// Remaining accounts are optional `(delegation, delegator_reputation,
+/// delegator_vote)` triples for users who delegated to the voter; the
+/// reputation and vote accounts are writable.
 #[derive(Accounts)]
 #[instruction(report_id: u64)]
 pub struct VoteOnReport<'info> {
@@ -10,7 +17,8 @@ pub struct VoteOnReport<'info> {
     pub voter: Signer<'info>,
     #[account(
         mut,
-        constraint = report.key() == report_id @ ErrorCode::ReportNotFound,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
         constraint = report.status == ReportStatus::Submitted @ ErrorCode::InvalidReportStatus
     )]
     pub report: Account<'info, Report>,
@@ -20,113 +28,1529 @@ pub struct VoteOnReport<'info> {
         bump
     )]
     pub voter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", report.submitter.as_ref()],
+        bump = submitter_history.bump
+    )]
+    pub submitter_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + Vote::LEN,
+        seeds = [b"vote", report.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, Vote>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct CastQuadraticVote<'info> {
     #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
+        constraint = report.status == ReportStatus::Submitted @ ErrorCode::InvalidReportStatus
+    )]
+    pub report: Account<'info, Report>,
+    #[account(
+        mut,
+        seeds = [b"reputation", voter.key().as_ref()],
+        bump
+    )]
+    pub voter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
     pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", report.submitter.as_ref()],
+        bump = submitter_history.bump
+    )]
+    pub submitter_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + Vote::LEN,
+        seeds = [b"vote", report.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, Vote>,
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + VoterCredits::LEN,
+        seeds = [b"voter_credits", voter.key().as_ref()],
+        bump
+    )]
+    pub voter_credits: Account<'info, VoterCredits>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
     pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum VoteType {
-    Upvote,
-    Downvote,
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct CommitVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
+        constraint = report.status == ReportStatus::Submitted @ ErrorCode::InvalidReportStatus
+    )]
+    pub report: Account<'info, Report>,
+    #[account(
+        mut,
+        seeds = [b"reputation", voter.key().as_ref()],
+        bump
+    )]
+    pub voter_reputation: Account<'info, UserReputation>,
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + Vote::LEN,
+        seeds = [b"vote", report.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, Vote>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct RevealVote<'info> {
+    pub voter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
+        constraint = report.status == ReportStatus::Submitted @ ErrorCode::InvalidReportStatus
+    )]
+    pub report: Account<'info, Report>,
+    #[account(
+        mut,
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", report.submitter.as_ref()],
+        bump = submitter_history.bump
+    )]
+    pub submitter_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        mut,
+        seeds = [b"vote", report.key().as_ref(), voter.key().as_ref()],
+        bump = vote.bump
+    )]
+    pub vote: Account<'info, Vote>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(report_id: u64, voter: Pubkey)]
+pub struct ForfeitUnrevealedVote<'info> {
+    #[account(
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump
+    )]
+    pub report: Account<'info, Report>,
+    #[account(
+        mut,
+        seeds = [b"vote", report.key().as_ref(), voter.as_ref()],
+        bump = vote.bump
+    )]
+    pub vote: Account<'info, Vote>,
+    #[account(
+        mut,
+        seeds = [b"reputation", voter.as_ref()],
+        bump
+    )]
+    pub voter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", voter.as_ref()],
+        bump = voter_history.bump
+    )]
+    pub voter_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct ChangeVote<'info> {
+    pub voter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
+        constraint = report.status == ReportStatus::Submitted @ ErrorCode::InvalidReportStatus
+    )]
+    pub report: Account<'info, Report>,
+    #[account(
+        mut,
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", report.submitter.as_ref()],
+        bump = submitter_history.bump
+    )]
+    pub submitter_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        mut,
+        seeds = [b"vote", report.key().as_ref(), voter.key().as_ref()],
+        bump = vote.bump
+    )]
+    pub vote: Account<'info, Vote>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct RetractVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
+        constraint = report.status == ReportStatus::Submitted @ ErrorCode::InvalidReportStatus
+    )]
+    pub report: Account<'info, Report>,
+    #[account(
+        mut,
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", report.submitter.as_ref()],
+        bump = submitter_history.bump
+    )]
+    pub submitter_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [b"vote", report.key().as_ref(), voter.key().as_ref()],
+        bump = vote.bump
+    )]
+    pub vote: Account<'info, Vote>,
+    /// CHECK: gets the vote account's rent back; the delegate who paid it
+    /// for a delegated vote, otherwise the voter
+    #[account(mut, address = vote.cast_by.unwrap_or_else(|| voter.key()))]
+    pub rent_recipient: AccountInfo<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct FinalizeReport<'info> {
+    #[account(
+        mut,
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump,
+        constraint = report.status == ReportStatus::Submitted @ ErrorCode::InvalidReportStatus
+    )]
+    pub report: Account<'info, Report>,
+    #[account(
+        mut,
+        seeds = [b"reputation", report.submitter.as_ref()],
+        bump
+    )]
+    pub submitter_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", report.submitter.as_ref()],
+        bump = submitter_history.bump
+    )]
+    pub submitter_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Remaining accounts are `(vote, voter_reputation, voter_history)` triples,
+/// all writable. Votes on an approval settle only after its fraud window, see
+/// `Report::fraud_window_end`, since a fraud finding swaps the winning side
+/// and settled incentives are not reversed. Rejections settle right away.
+#[derive(Accounts)]
+#[instruction(report_id: u64)]
+pub struct SettleVotes<'info> {
+    #[account(
+        seeds = [b"report", report_id.to_le_bytes().as_ref()],
+        bump = report.bump
+    )]
+    pub report: Account<'info, Report>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum VoteType {
+    Upvote,
+    Downvote,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum VotePhase {
+    /// Only the commitment is known; `vote_type` is not meaningful yet.
+    Committed,
+    /// The vote counts towards the report's tallies.
+    Revealed,
+    /// The commitment was never revealed and the voter was penalized.
+    Forfeited,
+}
+
+#[account]
+pub struct Vote {
+    pub voter: Pubkey,
+    pub report: Pubkey,
+    pub vote_type: VoteType,
+    pub weight: u64,
+    pub timestamp: i64,
+    pub bump: u8,
+    pub phase: VotePhase,
+    pub commitment: [u8; 32],
+    /// Set once the vote's incentive has been applied after finalization.
+    pub settled: bool,
+    /// Share of the validator reward pool earned by a winning vote.
+    pub reward_share: u64,
+    /// Delegate that cast this vote on the voter's behalf. They paid the
+    /// account's rent, so closing the account refunds them.
+    pub cast_by: Option<Pubkey>,
+    /// Number of votes bought with voice credits; 1 outside quadratic mode.
+    pub intensity: u64,
+}
+
+impl Vote {
+    pub const LEN: usize = 32 // voter
+        + 32 // report
+        + 1 // vote_type
+        + 8 // weight
+        + 8 // timestamp
+        + 1 // bump
+        + 1 // phase
+        + 32 // commitment
+        + 1 // settled
+        + 8 // reward_share
+        + (1 + 32) // cast_by
+        + 8; // intensity
+}
+
+/// Voice credits a voter has spent in the current epoch. The budget resets
+/// lazily on the first quadratic vote of a new epoch.
+#[account]
+pub struct VoterCredits {
+    pub voter: Pubkey,
+    pub bump: u8,
+    pub epoch: u64,
+    pub credits_spent: u64,
+}
+
+impl VoterCredits {
+    pub const LEN: usize = 32 // voter
+        + 1 // bump
+        + 8 // epoch
+        + 8; // credits_spent
+}
+
+/// Commitment a voter submits in commit-reveal mode. The voter's key is
+/// hashed in so a commitment cannot be copied and revealed by someone else.
+pub fn vote_commitment(vote_type: &VoteType, salt: &[u8; 32], voter: &Pubkey) -> [u8; 32] {
+    let vote_byte = [vote_type.clone() as u8];
+    hashv(&[&vote_byte, salt, voter.as_ref()]).to_bytes()
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum VoteWeightCurve {
+    /// One extra weight per `reputation_per_weight` points.
+    Linear,
+    /// Extra weight grows with the square root of the scaled reputation.
+    Sqrt,
+    /// Weight of the highest `weight_tiers` entry the voter reaches.
+    Tiered,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VoteWeightTier {
+    pub min_reputation: i64,
+    pub weight: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct VotingParams {
+    pub weight_curve: VoteWeightCurve,
+    /// Weight of a voter with zero reputation under `Linear` and `Sqrt`.
+    pub base_vote_weight: u64,
+    pub reputation_per_weight: u64,
+    /// Ordered by ascending `min_reputation`; used by `Tiered`.
+    pub weight_tiers: [VoteWeightTier; 3],
+    pub max_vote_weight: u64,
+    pub voting_period_seconds: i64,
+    /// Minimum total vote weight for a vote to settle the report.
+    pub quorum_weight: u64,
+    /// Share of the total weight that must be upvotes for approval.
+    pub approval_majority_bps: u16,
+    /// Categories that vote in two phases, indexed by `ReportCategory::index`.
+    pub commit_reveal_categories: [bool; 5],
+    pub reveal_period_seconds: i64,
+    /// Reputation lost for a commitment that is never revealed.
+    pub unrevealed_vote_penalty: i64,
+    /// Reputation gained by voting with the final outcome.
+    pub winning_vote_reputation_change: i64,
+    /// Reputation lost by voting against the final outcome.
+    pub losing_vote_reputation_change: i64,
+    /// New reports are voted on with `cast_quadratic_vote`.
+    pub quadratic_voting: bool,
+    pub voice_credits_per_epoch: u64,
+    pub credit_epoch_seconds: i64,
+}
+
+impl VotingParams {
+    pub const LEN: usize = 1 // weight_curve
+        + 8 // base_vote_weight
+        + 8 // reputation_per_weight
+        + (8 + 8) * 3 // weight_tiers
+        + 8 // max_vote_weight
+        + 8 // voting_period_seconds
+        + 8 // quorum_weight
+        + 2 // approval_majority_bps
+        + 5 // commit_reveal_categories
+        + 8 // reveal_period_seconds
+        + 8 // unrevealed_vote_penalty
+        + 8 // winning_vote_reputation_change
+        + 8 // losing_vote_reputation_change
+        + 1 // quadratic_voting
+        + 8 // voice_credits_per_epoch
+        + 8; // credit_epoch_seconds
+
+    pub fn validate(&self) -> Result<()> {
+        if self.reputation_per_weight == 0 || self.max_vote_weight == 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        if self.voting_period_seconds <= 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        if self.approval_majority_bps == 0 || self.approval_majority_bps > 10_000 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        if self.reveal_period_seconds <= 0 || self.unrevealed_vote_penalty < 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        if self.winning_vote_reputation_change < 0 || self.losing_vote_reputation_change < 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        if self.credit_epoch_seconds <= 0 || (self.quadratic_voting && self.voice_credits_per_epoch == 0) {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        let tiers_sorted = self
+            .weight_tiers
+            .windows(2)
+            .all(|pair| pair[0].min_reputation < pair[1].min_reputation);
+        if !tiers_sorted {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        Ok(())
+    }
+
+    /// Voting weight for a reputation score. Negative reputation carries no
+    /// weight under any curve.
+    pub fn vote_weight(&self, reputation_score: i64) -> u64 {
+        if reputation_score < 0 {
+            return 0;
+        }
+        let scaled = reputation_score as u64 / self.reputation_per_weight;
+        let weight = match self.weight_curve {
+            VoteWeightCurve::Linear => self.base_vote_weight.saturating_add(scaled),
+            VoteWeightCurve::Sqrt => self.base_vote_weight.saturating_add(integer_sqrt(scaled)),
+            VoteWeightCurve::Tiered => self
+                .weight_tiers
+                .iter()
+                .rev()
+                .find(|tier| reputation_score >= tier.min_reputation)
+                .map_or(0, |tier| tier.weight),
+        };
+        weight.min(self.max_vote_weight)
+    }
+}
+
+impl Default for VotingParams {
+    fn default() -> Self {
+        Self {
+            weight_curve: VoteWeightCurve::Linear,
+            base_vote_weight: 1,
+            reputation_per_weight: 100,
+            weight_tiers: [
+                VoteWeightTier { min_reputation: 0, weight: 1 },
+                VoteWeightTier { min_reputation: 100, weight: 2 },
+                VoteWeightTier { min_reputation: 500, weight: 3 },
+            ],
+            max_vote_weight: 10,
+            voting_period_seconds: 3 * 24 * 60 * 60,
+            quorum_weight: 5,
+            approval_majority_bps: 6_000,
+            commit_reveal_categories: [false, false, false, true, false],
+            reveal_period_seconds: 24 * 60 * 60,
+            unrevealed_vote_penalty: 5,
+            winning_vote_reputation_change: 2,
+            losing_vote_reputation_change: 1,
+            quadratic_voting: false,
+            voice_credits_per_epoch: 100,
+            credit_epoch_seconds: 30 * 24 * 60 * 60,
+        }
+    }
+}
+
+fn integer_sqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method, starting above the root
+    let mut x = value;
+    let mut y = value / 2 + value % 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+pub fn vote_on_report<'info>(ctx: Context<'_, '_, '_, 'info, VoteOnReport<'info>>, report_id: u64, vote_type: VoteType) -> Result<()> {
+    let report = &mut ctx.accounts.report;
+    let voter = &ctx.accounts.voter;
+    let voter_reputation = &mut ctx.accounts.voter_reputation;
+    let submitter_reputation = &mut ctx.accounts.submitter_reputation;
+    let vote = &mut ctx.accounts.vote;
+    let config = &ctx.accounts.config.params;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Check if the voter has already voted on this report
+    if has_voted(vote) {
+        return Err(ErrorCode::AlreadyVoted.into());
+    }
+
+    if report.commit_reveal {
+        return Err(ErrorCode::CommitRevealRequired.into());
+    }
+
+    if report.quadratic {
+        return Err(ErrorCode::QuadraticVotingRequired.into());
+    }
+
+    voter_reputation.apply_decay(config, now);
+    submitter_reputation.apply_decay(config, now);
+    config.eligibility.check_voter(voter_reputation, &report.submitter, now)?;
+
+    if now > report.voting_deadline {
+        return Err(ErrorCode::VotingClosed.into());
+    }
+
+    // Weight the vote by the voter's reputation before this vote changes it
+    let weight = voter_reputation.vote_weight(&report.category, config);
+
+    // Update weighted and raw tallies
+    tally_vote(report, &vote_type, weight);
+
+    // Update reputations
+    let change = update_reputations(submitter_reputation, &vote_type, &report.category, config, now)?;
+    let submitter_history = &mut ctx.accounts.submitter_history;
+    submitter_history.record(change, ReputationChangeReason::VoteReceived, Some(report_id), voter.key(), now);
+
+    // Record the vote
+    vote.voter = voter.key();
+    vote.report = report.key();
+    vote.vote_type = vote_type.clone();
+    vote.weight = weight;
+    vote.timestamp = now;
+    vote.bump = *ctx.bumps.get("vote").unwrap();
+    vote.phase = VotePhase::Revealed;
+    vote.intensity = 1;
+
+    // Emit an event for the vote
+    emit!(VoteCast {
+        report_id,
+        voter: *voter.key,
+        vote_type: vote_type.clone(),
+        weight,
+    });
+
+    // Cast the same vote for everyone who delegated to this voter
+    let triples = ctx.remaining_accounts.chunks_exact(3);
+    if !triples.remainder().is_empty() {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+    for triple in triples {
+        cast_delegated_vote(
+            report,
+            submitter_reputation,
+            submitter_history,
+            voter,
+            &ctx.accounts.system_program,
+            triple,
+            &vote_type,
+            config,
+            report_id,
+        )?;
+    }
+
+    msg!("Vote cast by {} on report {}", voter.key, report_id);
+
+    Ok(())
+}
+
+// Records a delegator's vote in their own vote PDA, paid for by the delegate,
+// so the delegator can still change or retract it while voting is open.
+// Delegators who already voted directly are skipped.
+#[allow(clippy::too_many_arguments)]
+fn cast_delegated_vote<'info>(
+    report: &mut Account<'info, Report>,
+    submitter_reputation: &mut Account<'info, UserReputation>,
+    submitter_history: &mut ReputationHistory,
+    voter: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    triple: &[AccountInfo<'info>],
+    vote_type: &VoteType,
+    config: &ConfigParams,
+    report_id: u64,
+) -> Result<()> {
+    let delegation = Account::<Delegation>::try_from(&triple[0])?;
+    let mut delegator_reputation = Account::<UserReputation>::try_from(&triple[1])?;
+    let delegator_vote = &triple[2];
+
+    // Delegation and reputation accounts are only ever created at their
+    // PDAs, so matching fields identify them
+    if delegator_reputation.user != delegation.delegator {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+    if delegation.delegate_for(&report.category) != Some(voter.key()) {
+        return Err(ErrorCode::NotDelegate.into());
+    }
+
+    let report_key = report.key();
+    let (vote_address, bump) = Pubkey::find_program_address(
+        &[b"vote", report_key.as_ref(), delegation.delegator.as_ref()],
+        &crate::ID,
+    );
+    if delegator_vote.key() != vote_address {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+
+    // A vote PDA owned by the program means the delegator voted directly.
+    // Lamports alone do not count, since anyone can fund the address.
+    // Delegators who could not vote themselves are skipped as well.
+    let now = Clock::get()?.unix_timestamp;
+    delegator_reputation.apply_decay(config, now);
+    delegator_reputation.exit(&crate::ID)?;
+    let weight = delegator_reputation.vote_weight(&report.category, config);
+    let eligible = config
+        .eligibility
+        .check_voter(&delegator_reputation, &report.submitter, now)
+        .is_ok();
+    let voted_directly = *delegator_vote.owner == crate::ID && !delegator_vote.data_is_empty();
+    if voted_directly || weight == 0 || !eligible {
+        return Ok(());
+    }
+
+    create_pda_account(
+        voter,
+        delegator_vote,
+        system_program,
+        &[b"vote", report_key.as_ref(), delegation.delegator.as_ref(), &[bump]],
+        8 + Vote::LEN,
+    )?;
+
+    tally_vote(report, vote_type, weight);
+    let change = update_reputations(submitter_reputation, vote_type, &report.category, config, now)?;
+    submitter_history.record(change, ReputationChangeReason::VoteReceived, Some(report_id), voter.key(), now);
+
+    let vote = Vote {
+        voter: delegation.delegator,
+        report: report_key,
+        vote_type: vote_type.clone(),
+        weight,
+        timestamp: now,
+        bump,
+        phase: VotePhase::Revealed,
+        commitment: [0; 32],
+        settled: false,
+        reward_share: 0,
+        cast_by: Some(voter.key()),
+        intensity: 1,
+    };
+    vote.try_serialize(&mut &mut delegator_vote.try_borrow_mut_data()?[..])?;
+
+    // Emit an event for the delegated vote
+    emit!(DelegatedVoteCast {
+        report_id,
+        delegator: delegation.delegator,
+        delegate: voter.key(),
+        vote_type: vote_type.clone(),
+        weight,
+    });
+
+    Ok(())
 }
 
-#[account]
-pub struct Vote {
-    pub voter: Pubkey,
-    pub report: Pubkey,
-    pub vote_type: VoteType,
-    pub timestamp: i64,
+// Creates a program-owned PDA paid for by `payer`. An address that already
+// holds lamports cannot go through create_account, so it is topped up to rent
+// exemption, allocated and assigned instead.
+fn create_pda_account<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let current = account.lamports();
+
+    if current == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+                &[seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    if current < rent {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent - current,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Assign {
+                account_to_assign: account.clone(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
 }
 
-pub fn vote_on_report(ctx: Context<VoteOnReport>, report_id: u64, vote_type: VoteType) -> Result<()> {
+/// Casts `intensity` votes at once for `intensity²` voice credits. Each vote
+/// carries the voter's reputation weight. Credits are not refunded when the
+/// vote is later retracted.
+pub fn cast_quadratic_vote(ctx: Context<CastQuadraticVote>, report_id: u64, vote_type: VoteType, intensity: u64) -> Result<()> {
     let report = &mut ctx.accounts.report;
     let voter = &ctx.accounts.voter;
-    let voter_reputation = &mut ctx.accounts.voter_reputation;
     let submitter_reputation = &mut ctx.accounts.submitter_reputation;
+    let vote = &mut ctx.accounts.vote;
+    let voter_credits = &mut ctx.accounts.voter_credits;
+    let config = &ctx.accounts.config.params;
 
-    // Check if the voter has already voted on this report
-    if has_voted(voter.key(), report_id)? {
+    let now = Clock::get()?.unix_timestamp;
+
+    if has_voted(vote) {
         return Err(ErrorCode::AlreadyVoted.into());
     }
 
-    // Update vote count
-    match vote_type {
-        VoteType::Upvote => report.votes += 1,
-        VoteType::Downvote => report.votes -= 1,
+    if !report.quadratic {
+        return Err(ErrorCode::QuadraticVotingNotEnabled.into());
     }
 
-    // Update reputations
-    update_reputations(voter_reputation, submitter_reputation, &vote_type)?;
+    if now > report.voting_deadline {
+        return Err(ErrorCode::VotingClosed.into());
+    }
+
+    if intensity == 0 {
+        return Err(ErrorCode::InvalidVoteIntensity.into());
+    }
+
+    let voter_reputation = &mut ctx.accounts.voter_reputation;
+    voter_reputation.apply_decay(config, now);
+    submitter_reputation.apply_decay(config, now);
+    config.eligibility.check_voter(voter_reputation, &report.submitter, now)?;
+
+    // Start a fresh budget on the first vote of a new epoch
+    let epoch = (now / config.voting.credit_epoch_seconds) as u64;
+    if voter_credits.voter == Pubkey::default() || voter_credits.epoch != epoch {
+        voter_credits.voter = voter.key();
+        voter_credits.bump = *ctx.bumps.get("voter_credits").unwrap();
+        voter_credits.epoch = epoch;
+        voter_credits.credits_spent = 0;
+    }
+
+    let cost = intensity.checked_mul(intensity).ok_or(ErrorCode::ArithmeticOverflow)?;
+    let credits_spent = voter_credits
+        .credits_spent
+        .checked_add(cost)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if credits_spent > config.voting.voice_credits_per_epoch {
+        return Err(ErrorCode::InsufficientVoiceCredits.into());
+    }
+    voter_credits.credits_spent = credits_spent;
+
+    let weight = voter_reputation
+        .vote_weight(&report.category, config)
+        .checked_mul(intensity)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    tally_vote(report, &vote_type, weight);
+    let change = update_reputations(submitter_reputation, &vote_type, &report.category, config, now)?;
+    let submitter_history = &mut ctx.accounts.submitter_history;
+    submitter_history.record(change, ReputationChangeReason::VoteReceived, Some(report_id), voter.key(), now);
 
     // Record the vote
-    record_vote(voter.key(), report_id, vote_type)?;
+    vote.voter = voter.key();
+    vote.report = report.key();
+    vote.vote_type = vote_type.clone();
+    vote.weight = weight;
+    vote.timestamp = now;
+    vote.bump = *ctx.bumps.get("vote").unwrap();
+    vote.phase = VotePhase::Revealed;
+    vote.intensity = intensity;
+
+    // Emit an event for the vote
+    emit!(QuadraticVoteCast {
+        report_id,
+        voter: *voter.key,
+        vote_type,
+        intensity,
+        credits_spent: cost,
+        weight,
+    });
+
+    Ok(())
+}
+
+pub fn commit_vote(ctx: Context<CommitVote>, report_id: u64, commitment: [u8; 32]) -> Result<()> {
+    let report = &ctx.accounts.report;
+    let voter = &ctx.accounts.voter;
+    let vote = &mut ctx.accounts.vote;
+    let config = &ctx.accounts.config.params;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if has_voted(vote) {
+        return Err(ErrorCode::AlreadyVoted.into());
+    }
+
+    if !report.commit_reveal {
+        return Err(ErrorCode::CommitRevealNotEnabled.into());
+    }
+
+    let voter_reputation = &mut ctx.accounts.voter_reputation;
+    voter_reputation.apply_decay(config, now);
+    config.eligibility.check_voter(voter_reputation, &report.submitter, now)?;
+
+    if now > report.voting_deadline {
+        return Err(ErrorCode::VotingClosed.into());
+    }
+
+    // Record the commitment; the weight is fixed now so later reputation
+    // changes cannot be timed around the reveal
+    vote.voter = voter.key();
+    vote.report = report.key();
+    vote.vote_type = VoteType::Upvote;
+    vote.weight = voter_reputation.vote_weight(&report.category, config);
+    vote.timestamp = now;
+    vote.bump = *ctx.bumps.get("vote").unwrap();
+    vote.phase = VotePhase::Committed;
+    vote.commitment = commitment;
+    vote.intensity = 1;
+
+    // Emit an event for the commitment
+    emit!(VoteCommitted {
+        report_id,
+        voter: *voter.key,
+    });
+
+    Ok(())
+}
+
+pub fn reveal_vote(ctx: Context<RevealVote>, report_id: u64, vote_type: VoteType, salt: [u8; 32]) -> Result<()> {
+    let report = &mut ctx.accounts.report;
+    let voter = &ctx.accounts.voter;
+    let submitter_reputation = &mut ctx.accounts.submitter_reputation;
+    let vote = &mut ctx.accounts.vote;
+    let config = &ctx.accounts.config.params;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if now <= report.voting_deadline || now > report.reveal_deadline {
+        return Err(ErrorCode::NotInRevealWindow.into());
+    }
+
+    if vote.phase != VotePhase::Committed {
+        return Err(ErrorCode::InvalidVotePhase.into());
+    }
+
+    if vote_commitment(&vote_type, &salt, voter.key) != vote.commitment {
+        return Err(ErrorCode::CommitmentMismatch.into());
+    }
 
-    // Check if the report should be approved or rejected based on votes
-    check_report_status(report, submitter_reputation)?;
+    // Count the vote with the weight fixed at commit time
+    submitter_reputation.apply_decay(config, now);
+    tally_vote(report, &vote_type, vote.weight);
+    let change = update_reputations(submitter_reputation, &vote_type, &report.category, config, now)?;
+    let submitter_history = &mut ctx.accounts.submitter_history;
+    submitter_history.record(change, ReputationChangeReason::VoteReceived, Some(report_id), voter.key(), now);
+
+    vote.vote_type = vote_type.clone();
+    vote.phase = VotePhase::Revealed;
 
     // Emit an event for the vote
     emit!(VoteCast {
         report_id,
         voter: *voter.key,
-        vote_type: vote_type.clone(),
+        vote_type,
+        weight: vote.weight,
     });
 
-    msg!("Vote cast by {} on report {}", voter.key, report_id);
+    Ok(())
+}
+
+pub fn forfeit_unrevealed_vote(ctx: Context<ForfeitUnrevealedVote>, report_id: u64, voter: Pubkey) -> Result<()> {
+    let report = &ctx.accounts.report;
+    let vote = &mut ctx.accounts.vote;
+    let voter_reputation = &mut ctx.accounts.voter_reputation;
+    let config = &ctx.accounts.config.params;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if now <= report.reveal_deadline {
+        return Err(ErrorCode::VotingStillOpen.into());
+    }
+
+    // Only penalize commitments that could have been revealed, i.e. the
+    // report stayed Submitted for the whole reveal window
+    if report.left_submitted_at.is_some_and(|left_at| left_at <= report.reveal_deadline) {
+        return Err(ErrorCode::InvalidReportStatus.into());
+    }
+
+    if vote.phase != VotePhase::Committed {
+        return Err(ErrorCode::InvalidVotePhase.into());
+    }
+
+    voter_reputation.apply_decay(config, now);
+    let penalty = config.voting.unrevealed_vote_penalty;
+    let category_change = voter_reputation.change_category_score(&report.category, -penalty, config, now);
+    let old_score = voter_reputation.reputation_score;
+    let penalized_score = old_score
+        .saturating_sub(penalty)
+        .clamp(config.min_reputation, config.max_reputation);
+    voter_reputation.set_score(penalized_score, now);
+    voter_reputation.refresh_tier(&config.tiers);
+    if let Some(entry) = ctx.accounts.voter_history.record(
+        voter_reputation.reputation_score - old_score,
+        ReputationChangeReason::UnrevealedVote,
+        Some(report_id),
+        Pubkey::default(),
+        now,
+    ) {
+        entry.set_category_change(report.category, category_change);
+    }
+    vote.phase = VotePhase::Forfeited;
+
+    // Emit an event for the forfeited vote
+    emit!(VoteForfeited {
+        report_id,
+        voter,
+        reputation_penalty: penalty,
+    });
+
+    Ok(())
+}
+
+pub fn change_vote(ctx: Context<ChangeVote>, report_id: u64, new_vote_type: VoteType) -> Result<()> {
+    let report = &mut ctx.accounts.report;
+    let voter = &ctx.accounts.voter;
+    let submitter_reputation = &mut ctx.accounts.submitter_reputation;
+    let vote = &mut ctx.accounts.vote;
+    let config = &ctx.accounts.config.params;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if now > report.voting_deadline {
+        return Err(ErrorCode::VotingClosed.into());
+    }
+
+    // Committed votes are changed by retracting and committing again
+    if vote.phase != VotePhase::Revealed {
+        return Err(ErrorCode::InvalidVotePhase.into());
+    }
+
+    if vote.vote_type == new_vote_type {
+        return Err(ErrorCode::VoteUnchanged.into());
+    }
+
+    // Undo the old vote and apply the new one with the same weight
+    submitter_reputation.apply_decay(config, now);
+    untally_vote(report, &vote.vote_type, vote.weight);
+    let withdrawn = revert_reputations(submitter_reputation, &vote.vote_type, &report.category, config, now)?;
+    tally_vote(report, &new_vote_type, vote.weight);
+    let received = update_reputations(submitter_reputation, &new_vote_type, &report.category, config, now)?;
+    let submitter_history = &mut ctx.accounts.submitter_history;
+    submitter_history.record(withdrawn, ReputationChangeReason::VoteWithdrawn, Some(report_id), voter.key(), now);
+    submitter_history.record(received, ReputationChangeReason::VoteReceived, Some(report_id), voter.key(), now);
+
+    let old_vote_type = std::mem::replace(&mut vote.vote_type, new_vote_type.clone());
+    vote.timestamp = now;
+
+    // Emit an event for the change
+    emit!(VoteChanged {
+        report_id,
+        voter: *voter.key,
+        old_vote_type,
+        new_vote_type,
+        weight: vote.weight,
+    });
 
     Ok(())
 }
 
-fn has_voted(voter: &Pubkey, report_id: u64) -> Result<bool> {
-    // TODO: Implement logic to check if the voter has already voted on this report
-    // This might involve checking a separate Vote account or a bitmap in the Report account
-    Ok(false)
+pub fn retract_vote(ctx: Context<RetractVote>, report_id: u64) -> Result<()> {
+    let report = &mut ctx.accounts.report;
+    let voter = &ctx.accounts.voter;
+    let submitter_reputation = &mut ctx.accounts.submitter_reputation;
+    let vote = &ctx.accounts.vote;
+    let config = &ctx.accounts.config.params;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if now > report.voting_deadline {
+        return Err(ErrorCode::VotingClosed.into());
+    }
+
+    // Only revealed votes have touched the tallies; a bare commitment is
+    // simply dropped. The account itself is closed to whoever paid its rent.
+    match vote.phase {
+        VotePhase::Revealed => {
+            submitter_reputation.apply_decay(config, now);
+            untally_vote(report, &vote.vote_type, vote.weight);
+            let change = revert_reputations(submitter_reputation, &vote.vote_type, &report.category, config, now)?;
+            let submitter_history = &mut ctx.accounts.submitter_history;
+            submitter_history.record(change, ReputationChangeReason::VoteWithdrawn, Some(report_id), voter.key(), now);
+        }
+        VotePhase::Committed => {}
+        VotePhase::Forfeited => return Err(ErrorCode::InvalidVotePhase.into()),
+    }
+
+    // Emit an event for the retraction
+    emit!(VoteRetracted {
+        report_id,
+        voter: *voter.key,
+    });
+
+    Ok(())
 }
 
-fn update_reputations(voter_reputation: &mut Account<UserReputation>, submitter_reputation: &mut Account<UserReputation>, vote_type: &VoteType) -> Result<()> {
+fn tally_vote(report: &mut Report, vote_type: &VoteType, weight: u64) {
+    report.total_vote_weight = report.total_vote_weight.saturating_add(weight);
     match vote_type {
         VoteType::Upvote => {
-            voter_reputation.reputation_score += VOTE_REPUTATION_CHANGE;
-            submitter_reputation.reputation_score += UPVOTE_REPUTATION_CHANGE;
+            report.votes = report.votes.saturating_add(weight as i64);
+            report.raw_votes += 1;
         }
         VoteType::Downvote => {
-            voter_reputation.reputation_score += VOTE_REPUTATION_CHANGE;
-            submitter_reputation.reputation_score -= DOWNVOTE_REPUTATION_CHANGE;
+            report.votes = report.votes.saturating_sub(weight as i64);
+            report.raw_votes -= 1;
         }
     }
-    Ok(())
 }
 
-fn record_vote(voter: Pubkey, report_id: u64, vote_type: VoteType) -> Result<()> {
-    // TODO: Implement logic to record the vote
-    // This might involve creating a new Vote account or updating a bitmap in the Report account
+fn untally_vote(report: &mut Report, vote_type: &VoteType, weight: u64) {
+    report.total_vote_weight = report.total_vote_weight.saturating_sub(weight);
+    match vote_type {
+        VoteType::Upvote => {
+            report.votes = report.votes.saturating_sub(weight as i64);
+            report.raw_votes -= 1;
+        }
+        VoteType::Downvote => {
+            report.votes = report.votes.saturating_add(weight as i64);
+            report.raw_votes += 1;
+        }
+    }
+}
+
+// The vote PDA is created on the first vote, so a populated voter field means
+// this (report, voter) pair has already been recorded.
+fn has_voted(vote: &Vote) -> bool {
+    vote.voter != Pubkey::default()
+}
+
+/// Total upvote and downvote weight behind the report's tallies.
+pub fn side_weights(report: &Report) -> (u64, u64) {
+    let upvote_weight = (report.total_vote_weight as i128 + report.votes as i128) / 2;
+    let downvote_weight = report.total_vote_weight as i128 - upvote_weight;
+    (upvote_weight as u64, downvote_weight as u64)
+}
+
+// Both return the change applied so callers can record it in the history
+fn update_reputations(submitter_reputation: &mut Account<UserReputation>, vote_type: &VoteType, category: &ReportCategory, config: &ConfigParams, now: i64) -> Result<i64> {
+    let change = vote_reputation_change(vote_type, config);
+    Ok(apply_submitter_change(submitter_reputation, change, category, config, now).0)
+}
+
+fn revert_reputations(submitter_reputation: &mut Account<UserReputation>, vote_type: &VoteType, category: &ReportCategory, config: &ConfigParams, now: i64) -> Result<i64> {
+    let change = -vote_reputation_change(vote_type, config);
+    Ok(apply_submitter_change(submitter_reputation, change, category, config, now).0)
+}
+
+// Applies `change` to the global and category scores within the configured
+// bounds and returns the changes actually applied to each
+fn apply_submitter_change(submitter_reputation: &mut UserReputation, change: i64, category: &ReportCategory, config: &ConfigParams, now: i64) -> (i64, i64) {
+    let category_change = submitter_reputation.change_category_score(category, change, config, now);
+    let old_score = submitter_reputation.reputation_score;
+    let new_score = old_score
+        .saturating_add(change)
+        .clamp(config.min_reputation, config.max_reputation);
+    submitter_reputation.set_score(new_score, now);
+    submitter_reputation.refresh_tier(&config.tiers);
+    (submitter_reputation.reputation_score - old_score, category_change)
+}
+
+// Submitter reputation change caused by a single vote
+fn vote_reputation_change(vote_type: &VoteType, config: &ConfigParams) -> i64 {
+    match vote_type {
+        VoteType::Upvote => config.upvote_reputation_change,
+        VoteType::Downvote => -config.downvote_reputation_change,
+    }
+}
+
+pub fn finalize_report(ctx: Context<FinalizeReport>, report_id: u64) -> Result<()> {
+    let report = &mut ctx.accounts.report;
+    let submitter_reputation = &mut ctx.accounts.submitter_reputation;
+    let config = &ctx.accounts.config.params;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if now <= report.reveal_deadline {
+        return Err(ErrorCode::VotingStillOpen.into());
+    }
+
+    let outcome = settle_outcome(report, config);
+    transition_report_status(report, outcome.clone())?;
+    if !report.is_open() {
+        submitter_reputation.report_closed();
+    }
+
+    // Remember the winning side so voter incentives can be settled in batches
+    let (upvote_weight, downvote_weight) = side_weights(report);
+    match outcome {
+        ReportStatus::Approved => {
+            report.winning_vote = Some(VoteType::Upvote);
+            report.winning_weight = upvote_weight;
+        }
+        ReportStatus::Rejected => {
+            report.winning_vote = Some(VoteType::Downvote);
+            report.winning_weight = downvote_weight;
+        }
+        _ => {}
+    }
+
+    // Apply the submitter's reputation change for the outcome
+    submitter_reputation.apply_decay(config, now);
+    let outcome_change = match outcome {
+        ReportStatus::Approved => Some((config.report_approved_reputation_change, ReputationChangeReason::ReportApproved)),
+        ReportStatus::Rejected => Some((-config.report_rejected_reputation_change, ReputationChangeReason::ReportRejected)),
+        _ => None,
+    };
+    if let Some((change, reason)) = outcome_change {
+        let (applied, category_change) = apply_submitter_change(submitter_reputation, change, &report.category, config, now);
+        if let Some(entry) = ctx
+            .accounts
+            .submitter_history
+            .record(applied, reason, Some(report_id), Pubkey::default(), now)
+        {
+            entry.set_category_change(report.category, category_change);
+        }
+    }
+
+    // Emit an event for the finalization
+    emit!(ReportFinalized {
+        report_id,
+        outcome,
+        votes: report.votes,
+        total_vote_weight: report.total_vote_weight,
+    });
+
     Ok(())
 }
 
-fn check_report_status(report: &mut Account<Report>, submitter_reputation: &mut Account<UserReputation>) -> Result<()> {
-    if report.votes >= APPROVAL_THRESHOLD {
-        report.status = ReportStatus::Approved;
-        submitter_reputation.reputation_score += REPORT_APPROVED_REPUTATION_CHANGE;
-    } else if report.votes <= REJECTION_THRESHOLD {
-        report.status = ReportStatus::Rejected;
-        submitter_reputation.reputation_score -= REPORT_REJECTED_REPUTATION_CHANGE;
+pub fn settle_votes<'info>(ctx: Context<'_, '_, '_, 'info, SettleVotes<'info>>, report_id: u64) -> Result<()> {
+    let report = &ctx.accounts.report;
+    let config = &ctx.accounts.config.params;
+
+    let winning_vote = match &report.winning_vote {
+        Some(winning_vote) => winning_vote.clone(),
+        None => return Err(ErrorCode::ReportNotFinalized.into()),
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    if winning_vote == VoteType::Upvote && now <= report.fraud_window_end(&config.rewards) {
+        return Err(ErrorCode::FraudWindowOpen.into());
+    }
+
+    let triples = ctx.remaining_accounts.chunks_exact(3);
+    if !triples.remainder().is_empty() {
+        return Err(ErrorCode::InvalidRemainingAccounts.into());
+    }
+
+    let pool = config.rewards.validator_reward_pool as u128;
+    let mut settled_count: u32 = 0;
+
+    for triple in triples {
+        let mut vote = Account::<Vote>::try_from(&triple[0])?;
+        let mut voter_reputation = Account::<UserReputation>::try_from(&triple[1])?;
+        let mut voter_history = Account::<ReputationHistory>::try_from(&triple[2])?;
+
+        // Vote, reputation and history accounts are only ever created at
+        // their PDAs, so matching fields identify them
+        if vote.report != report.key() || voter_reputation.user != vote.voter || voter_history.user != vote.voter {
+            return Err(ErrorCode::InvalidRemainingAccounts.into());
+        }
+
+        // Unrevealed commitments are handled by forfeit_unrevealed_vote
+        if vote.settled || vote.phase != VotePhase::Revealed {
+            continue;
+        }
+
+        voter_reputation.apply_decay(config, now);
+        let (change, reason) = if vote.vote_type == winning_vote {
+            vote.reward_share = if report.winning_weight > 0 {
+                (pool * vote.weight as u128 / report.winning_weight as u128) as u64
+            } else {
+                0
+            };
+            (config.voting.winning_vote_reputation_change, ReputationChangeReason::VoteWon)
+        } else {
+            (-config.voting.losing_vote_reputation_change, ReputationChangeReason::VoteLost)
+        };
+        voter_reputation.change_category_score(&report.category, change, config, now);
+        let old_score = voter_reputation.reputation_score;
+        let new_score = old_score
+            .saturating_add(change)
+            .clamp(config.min_reputation, config.max_reputation);
+        voter_reputation.set_score(new_score, now);
+        voter_reputation.refresh_tier(&config.tiers);
+        voter_history.record(
+            voter_reputation.reputation_score - old_score,
+            reason,
+            Some(report_id),
+            Pubkey::default(),
+            now,
+        );
+        vote.settled = true;
+        settled_count += 1;
+
+        vote.exit(&crate::ID)?;
+        voter_reputation.exit(&crate::ID)?;
+        voter_history.exit(&crate::ID)?;
     }
+
+    // Emit an event for the settled batch
+    emit!(VotesSettled {
+        report_id,
+        winning_vote,
+        settled_count,
+    });
+
     Ok(())
 }
 
+// Without quorum the report is left for the authority to review. Otherwise it
+// is approved when upvotes hold the configured majority of the weight and the
+// net margin reaches the approval threshold. A majority whose margin falls
+// short is left for review as well rather than rejected.
+fn settle_outcome(report: &Report, config: &ConfigParams) -> ReportStatus {
+    let total = report.total_vote_weight as i128;
+    if report.total_vote_weight < config.voting.quorum_weight {
+        return ReportStatus::UnderReview;
+    }
+
+    let upvote_weight = (total + report.votes as i128) / 2;
+    let has_majority = upvote_weight * 10_000 >= total * config.voting.approval_majority_bps as i128;
+
+    match (has_majority, report.votes >= config.approval_threshold) {
+        (true, true) => ReportStatus::Approved,
+        (true, false) => ReportStatus::UnderReview,
+        (false, _) => ReportStatus::Rejected,
+    }
+}
+
 // Event emitted when a vote is cast
 #[event]
 pub struct VoteCast {
     pub report_id: u64,
     pub voter: Pubkey,
     pub vote_type: VoteType,
+    pub weight: u64,
+}
+
+// Event emitted when a quadratic vote is cast
+#[event]
+pub struct QuadraticVoteCast {
+    pub report_id: u64,
+    pub voter: Pubkey,
+    pub vote_type: VoteType,
+    pub intensity: u64,
+    pub credits_spent: u64,
+    pub weight: u64,
+}
+
+// Event emitted when a delegate's vote is counted for a delegator
+#[event]
+pub struct DelegatedVoteCast {
+    pub report_id: u64,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub vote_type: VoteType,
+    pub weight: u64,
+}
+
+// Event emitted when a voter changes their vote
+#[event]
+pub struct VoteChanged {
+    pub report_id: u64,
+    pub voter: Pubkey,
+    pub old_vote_type: VoteType,
+    pub new_vote_type: VoteType,
+    pub weight: u64,
+}
+
+// Event emitted when a voter retracts their vote
+#[event]
+pub struct VoteRetracted {
+    pub report_id: u64,
+    pub voter: Pubkey,
+}
+
+// Event emitted when a commit-reveal vote is committed
+#[event]
+pub struct VoteCommitted {
+    pub report_id: u64,
+    pub voter: Pubkey,
+}
+
+// Event emitted when an unrevealed commitment is penalized
+#[event]
+pub struct VoteForfeited {
+    pub report_id: u64,
+    pub voter: Pubkey,
+    pub reputation_penalty: i64,
+}
+
+// Event emitted for each batch of votes settled after finalization
+#[event]
+pub struct VotesSettled {
+    pub report_id: u64,
+    pub winning_vote: VoteType,
+    pub settled_count: u32,
+}
+
+// Event emitted when a report's vote is settled
+#[event]
+pub struct ReportFinalized {
+    pub report_id: u64,
+    pub outcome: ReportStatus,
+    pub votes: i64,
+    pub total_vote_weight: u64,
 }
 
-// Constants
-const VOTE_REPUTATION_CHANGE: i64 = 1;
-const UPVOTE_REPUTATION_CHANGE: i64 = 5;
-const DOWNVOTE_REPUTATION_CHANGE: i64 = 2;
-const APPROVAL_THRESHOLD: i64 = 5;
-const REJECTION_THRESHOLD: i64 = -3;
-const REPORT_APPROVED_REPUTATION_CHANGE: i64 = 20;
-const REPORT_REJECTED_REPUTATION_CHANGE: i64 = 10;
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_sqrt_rounds_down() {
+        for value in 0..10_000u64 {
+            let root = integer_sqrt(value);
+            assert!(root * root <= value && (root + 1) * (root + 1) > value, "sqrt({})", value);
+        }
+    }
+
+    #[test]
+    fn integer_sqrt_handles_large_values() {
+        assert_eq!(integer_sqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(integer_sqrt(u32::MAX as u64 * u32::MAX as u64), u32::MAX as u64);
+        assert_eq!(integer_sqrt(1 << 62), 1 << 31);
+    }
+
+    #[test]
+    fn linear_weight_grows_with_reputation_up_to_cap() {
+        let params = VotingParams::default();
+        assert_eq!(params.vote_weight(0), 1);
+        assert_eq!(params.vote_weight(99), 1);
+        assert_eq!(params.vote_weight(250), 3);
+        assert_eq!(params.vote_weight(5_000), 10);
+        assert_eq!(params.vote_weight(i64::MAX), 10);
+    }
+
+    #[test]
+    fn sqrt_weight_grows_with_root_of_reputation() {
+        let params = VotingParams {
+            weight_curve: VoteWeightCurve::Sqrt,
+            ..VotingParams::default()
+        };
+        assert_eq!(params.vote_weight(0), 1);
+        assert_eq!(params.vote_weight(399), 2);
+        assert_eq!(params.vote_weight(900), 4);
+        assert_eq!(params.vote_weight(i64::MAX), 10);
+    }
+
+    #[test]
+    fn tiered_weight_uses_highest_tier_reached() {
+        let params = VotingParams {
+            weight_curve: VoteWeightCurve::Tiered,
+            ..VotingParams::default()
+        };
+        assert_eq!(params.vote_weight(0), 1);
+        assert_eq!(params.vote_weight(99), 1);
+        assert_eq!(params.vote_weight(100), 2);
+        assert_eq!(params.vote_weight(500), 3);
+
+        // Scores below the lowest tier carry no weight
+        let mut params = params;
+        params.weight_tiers[0].min_reputation = 10;
+        assert_eq!(params.vote_weight(9), 0);
+    }
+
+    fn report_with_votes(upvotes: u64, downvotes: u64) -> Report {
+        Report {
+            report_id: 1,
+            bump: 0,
+            submitter: Pubkey::default(),
+            description: String::new(),
+            location: String::new(),
+            media_hash: String::new(),
+            timestamp: 0,
+            votes: upvotes as i64 - downvotes as i64,
+            raw_votes: upvotes as i64 - downvotes as i64,
+            total_vote_weight: upvotes + downvotes,
+            voting_deadline: 0,
+            reveal_deadline: 0,
+            commit_reveal: false,
+            quadratic: false,
+            winning_vote: None,
+            winning_weight: 0,
+            category: ReportCategory::RoadIssue,
+            status: ReportStatus::Submitted,
+            left_submitted_at: None,
+            escalation: None,
+            reward_distributed: false,
+            bond: 0,
+        }
+    }
+
+    #[test]
+    fn outcome_without_quorum_is_left_for_review() {
+        let config = ConfigParams::default();
+        assert!(settle_outcome(&report_with_votes(4, 0), &config) == ReportStatus::UnderReview);
+        assert!(settle_outcome(&report_with_votes(0, 4), &config) == ReportStatus::UnderReview);
+    }
+
+    #[test]
+    fn outcome_approves_majority_reaching_threshold() {
+        let config = ConfigParams::default();
+        assert!(settle_outcome(&report_with_votes(5, 0), &config) == ReportStatus::Approved);
+        assert!(settle_outcome(&report_with_votes(8, 3), &config) == ReportStatus::Approved);
+    }
+
+    #[test]
+    fn outcome_leaves_majority_below_threshold_for_review() {
+        let config = ConfigParams::default();
+        assert!(settle_outcome(&report_with_votes(5, 1), &config) == ReportStatus::UnderReview);
+        assert!(settle_outcome(&report_with_votes(6, 4), &config) == ReportStatus::UnderReview);
+    }
+
+    #[test]
+    fn outcome_rejects_without_majority() {
+        let config = ConfigParams::default();
+        assert!(settle_outcome(&report_with_votes(5, 5), &config) == ReportStatus::Rejected);
+        assert!(settle_outcome(&report_with_votes(0, 6), &config) == ReportStatus::Rejected);
+    }
+
+    #[test]
+    fn negative_reputation_carries_no_weight() {
+        for weight_curve in [VoteWeightCurve::Linear, VoteWeightCurve::Sqrt, VoteWeightCurve::Tiered] {
+            let params = VotingParams {
+                weight_curve,
+                ..VotingParams::default()
+            };
+            assert_eq!(params.vote_weight(-1), 0);
+            assert_eq!(params.vote_weight(i64::MIN), 0);
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use anchor_lang::prelude::*;
+use crate::config::{Config, ConfigParams};
+use crate::reputation::{ReputationChangeReason, ReputationHistory, UserReputation};
+use crate::tiers::ReputationTier;
+use crate::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(newcomer: Pubkey)]
+pub struct VouchForUser<'info> {
+    #[account(mut)]
+    pub voucher: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"reputation", voucher.key().as_ref()],
+        bump
+    )]
+    pub voucher_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", voucher.key().as_ref()],
+        bump = voucher_history.bump
+    )]
+    pub voucher_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        mut,
+        seeds = [b"reputation", newcomer.as_ref()],
+        bump,
+        constraint = newcomer_reputation.user == newcomer @ ErrorCode::UserMismatch
+    )]
+    pub newcomer_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", newcomer.as_ref()],
+        bump = newcomer_history.bump
+    )]
+    pub newcomer_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        init,
+        payer = voucher,
+        space = 8 + Vouch::LEN,
+        seeds = [b"vouch", newcomer.as_ref()],
+        bump
+    )]
+    pub vouch: Account<'info, Vouch>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Settles a vouch in either direction; permissionless so anyone can crank it.
+#[derive(Accounts)]
+#[instruction(newcomer: Pubkey)]
+pub struct SettleVouch<'info> {
+    #[account(
+        mut,
+        seeds = [b"vouch", newcomer.as_ref()],
+        bump = vouch.bump,
+        constraint = vouch.status == VouchStatus::Active @ ErrorCode::VouchNotActive
+    )]
+    pub vouch: Account<'info, Vouch>,
+    #[account(
+        mut,
+        seeds = [b"reputation", vouch.voucher.as_ref()],
+        bump
+    )]
+    pub voucher_reputation: Account<'info, UserReputation>,
+    #[account(
+        mut,
+        seeds = [b"reputation_history", vouch.voucher.as_ref()],
+        bump = voucher_history.bump
+    )]
+    pub voucher_history: Box<Account<'info, ReputationHistory>>,
+    #[account(
+        seeds = [b"reputation", newcomer.as_ref()],
+        bump
+    )]
+    pub newcomer_reputation: Account<'info, UserReputation>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum VouchStatus {
+    /// The stake is locked and lost if the newcomer falls below the threshold.
+    Active,
+    /// The lock period passed and the stake went back to the voucher.
+    Released,
+    /// The newcomer fell below the threshold and the stake was forfeited.
+    Slashed,
+}
+
+/// A newcomer can be vouched for once; the account is kept after settlement
+/// as a record of the trust edge.
+#[account]
+pub struct Vouch {
+    pub voucher: Pubkey,
+    pub newcomer: Pubkey,
+    pub bump: u8,
+    /// Reputation taken from the voucher while the vouch is active.
+    pub stake: i64,
+    pub created_at: i64,
+    pub status: VouchStatus,
+}
+
+impl Vouch {
+    pub const LEN: usize = 32 // voucher
+        + 32 // newcomer
+        + 1 // bump
+        + 8 // stake
+        + 8 // created_at
+        + 1; // status
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct VouchParams {
+    pub minimum_reputation_for_vouching: i64,
+    pub min_stake: i64,
+    /// Reputation granted to the newcomer when vouched for.
+    pub newcomer_bonus: i64,
+    /// The voucher's stake is slashed if the newcomer's score drops below this.
+    pub slash_threshold: i64,
+    /// How long the stake stays at risk before it can be released.
+    pub lock_period_seconds: i64,
+}
+
+impl VouchParams {
+    pub const LEN: usize = 8 // minimum_reputation_for_vouching
+        + 8 // min_stake
+        + 8 // newcomer_bonus
+        + 8 // slash_threshold
+        + 8; // lock_period_seconds
+
+    pub fn validate(&self) -> Result<()> {
+        if self.min_stake <= 0 || self.newcomer_bonus < 0 || self.lock_period_seconds < 0 {
+            return Err(ErrorCode::InvalidConfig.into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for VouchParams {
+    fn default() -> Self {
+        Self {
+            minimum_reputation_for_vouching: 200,
+            min_stake: 50,
+            newcomer_bonus: 25,
+            slash_threshold: -50,
+            lock_period_seconds: 90 * 24 * 60 * 60,
+        }
+    }
+}
+
+pub fn vouch_for_user(ctx: Context<VouchForUser>, newcomer: Pubkey, stake: i64) -> Result<()> {
+    let voucher = &ctx.accounts.voucher;
+    let voucher_reputation = &mut ctx.accounts.voucher_reputation;
+    let newcomer_reputation = &mut ctx.accounts.newcomer_reputation;
+    let vouch = &mut ctx.accounts.vouch;
+    let config = &ctx.accounts.config.params;
+
+    if newcomer == voucher.key() {
+        return Err(ErrorCode::SelfVouch.into());
+    }
+
+    voucher_reputation.is_active()?;
+    newcomer_reputation.is_active()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    voucher_reputation.apply_decay(config, now);
+    newcomer_reputation.apply_decay(config, now);
+
+    if voucher_reputation.reputation_score < config.vouching.minimum_reputation_for_vouching {
+        return Err(ErrorCode::InsufficientReputation.into());
+    }
+
+    // Only users still in the Newcomer tier can be vouched for
+    if config.tiers.tier_for(newcomer_reputation.reputation_score) != ReputationTier::Newcomer {
+        return Err(ErrorCode::NotNewcomer.into());
+    }
+
+    // The stake must be affordable from the voucher's current score
+    if stake < config.vouching.min_stake || stake > voucher_reputation.reputation_score {
+        return Err(ErrorCode::InvalidVouchStake.into());
+    }
+
+    let voucher_old_score = voucher_reputation.reputation_score;
+    voucher_reputation.set_score(voucher_old_score - stake, now);
+    voucher_reputation.active_vouches += 1;
+    let newcomer_old_score = newcomer_reputation.reputation_score;
+    newcomer_reputation.set_score(
+        clamp_reputation(newcomer_old_score.saturating_add(config.vouching.newcomer_bonus), config),
+        now,
+    );
+    voucher_reputation.refresh_tier(&config.tiers);
+    newcomer_reputation.refresh_tier(&config.tiers);
+
+    ctx.accounts
+        .voucher_history
+        .record(-stake, ReputationChangeReason::VouchStake, None, voucher.key(), now);
+    ctx.accounts.newcomer_history.record(
+        newcomer_reputation.reputation_score - newcomer_old_score,
+        ReputationChangeReason::VouchBonus,
+        None,
+        voucher.key(),
+        now,
+    );
+
+    vouch.voucher = voucher.key();
+    vouch.newcomer = newcomer;
+    vouch.bump = *ctx.bumps.get("vouch").unwrap();
+    vouch.stake = stake;
+    vouch.created_at = now;
+    vouch.status = VouchStatus::Active;
+
+    // Emit an event for the vouch
+    emit!(UserVouched {
+        voucher: voucher.key(),
+        newcomer,
+        stake,
+        newcomer_bonus: config.vouching.newcomer_bonus,
+    });
+
+    Ok(())
+}
+
+/// Returns the stake to the voucher once the lock period has passed with the
+/// newcomer still at or above the slash threshold.
+pub fn release_vouch(ctx: Context<SettleVouch>, newcomer: Pubkey) -> Result<()> {
+    let vouch = &mut ctx.accounts.vouch;
+    let voucher_reputation = &mut ctx.accounts.voucher_reputation;
+    let config = &ctx.accounts.config.params;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    if now < vouch.created_at.saturating_add(config.vouching.lock_period_seconds) {
+        return Err(ErrorCode::VouchStillLocked.into());
+    }
+
+    if ctx.accounts.newcomer_reputation.effective_score(config, now) < config.vouching.slash_threshold {
+        return Err(ErrorCode::NewcomerBelowSlashThreshold.into());
+    }
+
+    voucher_reputation.apply_decay(config, now);
+    let old_score = voucher_reputation.reputation_score;
+    voucher_reputation.set_score(clamp_reputation(old_score.saturating_add(vouch.stake), config), now);
+    voucher_reputation.refresh_tier(&config.tiers);
+    voucher_reputation.active_vouches = voucher_reputation.active_vouches.saturating_sub(1);
+    ctx.accounts.voucher_history.record(
+        voucher_reputation.reputation_score - old_score,
+        ReputationChangeReason::VouchReleased,
+        None,
+        Pubkey::default(),
+        now,
+    );
+    vouch.status = VouchStatus::Released;
+
+    // Emit an event for the settled vouch
+    emit!(VouchSettled {
+        voucher: vouch.voucher,
+        newcomer,
+        stake: vouch.stake,
+        status: VouchStatus::Released,
+    });
+
+    Ok(())
+}
+
+/// Forfeits the stake of an active vouch whose newcomer has been penalized
+/// below the slash threshold.
+pub fn slash_vouch(ctx: Context<SettleVouch>, newcomer: Pubkey) -> Result<()> {
+    let vouch = &mut ctx.accounts.vouch;
+    let config = &ctx.accounts.config.params;
+
+    let newcomer_score = ctx
+        .accounts
+        .newcomer_reputation
+        .effective_score(config, Clock::get()?.unix_timestamp);
+    if newcomer_score >= config.vouching.slash_threshold {
+        return Err(ErrorCode::NewcomerAboveSlashThreshold.into());
+    }
+
+    // The stake was already taken from the voucher; it is simply not returned
+    vouch.status = VouchStatus::Slashed;
+    let voucher_reputation = &mut ctx.accounts.voucher_reputation;
+    voucher_reputation.active_vouches = voucher_reputation.active_vouches.saturating_sub(1);
+
+    // Emit an event for the settled vouch
+    emit!(VouchSettled {
+        voucher: vouch.voucher,
+        newcomer,
+        stake: vouch.stake,
+        status: VouchStatus::Slashed,
+    });
+
+    Ok(())
+}
+
+fn clamp_reputation(score: i64, config: &ConfigParams) -> i64 {
+    score.clamp(config.min_reputation, config.max_reputation)
+}
+
+// Event emitted when an established user vouches for a newcomer
+#[event]
+pub struct UserVouched {
+    pub voucher: Pubkey,
+    pub newcomer: Pubkey,
+    pub stake: i64,
+    pub newcomer_bonus: i64,
+}
+
+// Event emitted when a vouch is released or slashed
+#[event]
+pub struct VouchSettled {
+    pub voucher: Pubkey,
+    pub newcomer: Pubkey,
+    pub stake: i64,
+    pub status: VouchStatus,
+}